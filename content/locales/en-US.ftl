@@ -0,0 +1,14 @@
+layout_root_asidebar_nav_articles = Blog
+layout_root_asidebar_nav_tags = Tag
+layout_root_asidebar_nav_about = About
+layout_root_asidebar_nav_search = Search
+blog_article_outdated_notice = The { $source } version has changed since this translation was made
+blog_article_outdated_original = Read the original
+blog_article_translation_pending = Translation pending
+blog_translations_fallback_notice = Not available in { $requested } • showing { $served }
+blog_translations_dismiss = Dismiss
+blog_translations_label = Translations
+blog_translations_also_in = Also in
+blog_preview_scheduled = Scheduled for { $publish_at }
+blog_preview_draft = Draft
+blog_preview_notice = Preview • { $status } • not publicly listed
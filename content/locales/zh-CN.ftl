@@ -0,0 +1,14 @@
+layout_root_asidebar_nav_articles = 文
+layout_root_asidebar_nav_tags = 集
+layout_root_asidebar_nav_about = 我
+layout_root_asidebar_nav_search = 搜
+blog_article_outdated_notice = { $source }原文在本译文完成后已有更新
+blog_article_outdated_original = 阅读原文
+blog_article_translation_pending = 翻译中
+blog_translations_fallback_notice = 暂无{ $requested }版本 • 当前显示{ $served }
+blog_translations_dismiss = 关闭
+blog_translations_label = 其他语言版本
+blog_translations_also_in = 其他语言
+blog_preview_scheduled = 定于{ $publish_at }发布
+blog_preview_draft = 草稿
+blog_preview_notice = 预览 • { $status } • 未公开列出
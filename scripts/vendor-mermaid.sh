@@ -0,0 +1,35 @@
+#!/usr/bin/env sh
+# Downloads the pinned Mermaid bundle served by `MermaidScript` and records
+# its checksum next to it. Commit both files. The version is the `mermaid`
+# dependency in scripts/mermaid-cli/package.json, which the diagram
+# pre-renderer also uses; bump it there and rerun this script to upgrade.
+#
+#   scripts/vendor-mermaid.sh           download and record the checksum
+#   scripts/vendor-mermaid.sh --verify  check the committed copy, offline
+set -eu
+
+MERMAID_VERSION="$(sed -n 's/^ *"mermaid": "\([0-9][^"]*\)".*/\1/p' "$(dirname "$0")/mermaid-cli/package.json")"
+DIR="$(dirname "$0")/../assets/vendor/mermaid"
+TARGET="$DIR/mermaid.min.js"
+CHECKSUM="$DIR/mermaid.min.js.sha256"
+
+if [ "${1:-}" = "--verify" ]; then
+  if [ ! -f "$TARGET" ] || [ ! -f "$CHECKSUM" ]; then
+    echo "missing $TARGET or its checksum; run scripts/vendor-mermaid.sh and commit the result" >&2
+    exit 1
+  fi
+  grep -q "mermaid@${MERMAID_VERSION}" "$CHECKSUM" || {
+    echo "vendored mermaid is not version ${MERMAID_VERSION}" >&2
+    exit 1
+  }
+  (cd "$DIR" && grep -v '^#' mermaid.min.js.sha256 | sha256sum -c -)
+  exit 0
+fi
+
+mkdir -p "$DIR"
+curl -fsSL "https://cdn.jsdelivr.net/npm/mermaid@${MERMAID_VERSION}/dist/mermaid.min.js" -o "$TARGET"
+{
+  echo "# mermaid@${MERMAID_VERSION}"
+  (cd "$DIR" && sha256sum mermaid.min.js)
+} > "$CHECKSUM"
+echo "vendored mermaid ${MERMAID_VERSION} to ${TARGET}"
@@ -1,9 +1,18 @@
-use dioxus::fullstack::{FileStream, Redirect};
+use dioxus::fullstack::FileStream;
 use dioxus::prelude::*;
 
 #[cfg(feature = "server")]
 use crate::impls::blog;
-use crate::impls::blog::{Post, PostMetadata, TagGroup};
+use crate::impls::blog::{Post, PostMetadata, PostNeighbors, Series, ServedPost, TagGroup};
+#[cfg(feature = "server")]
+use crate::impls::locale_routes::is_localizable_path;
+#[cfg(feature = "server")]
+use crate::impls::math::{prerender_math, MathTarget};
+#[cfg(feature = "server")]
+use crate::impls::mermaid::prerender_diagrams;
+#[cfg(feature = "server")]
+use crate::impls::search;
+use crate::impls::search::SearchHit;
 #[cfg(feature = "server")]
 use crate::root::Route;
 
@@ -14,12 +23,53 @@ pub async fn get_posts_by_lang(lang: String) -> ServerFnResult<Vec<PostMetadata>
 
 #[get("/api/blog/post/:slug/:lang")]
 pub async fn get_post_by_slug_and_lang(slug: String, lang: String) -> ServerFnResult<Option<Post>> {
-    Ok(blog::get_post_by_slug_and_lang(&slug, &lang))
+    Ok(blog::get_post_by_slug_and_lang(&slug, &lang).map(|post| with_rendered_content(&post)))
 }
 
 #[get("/api/blog/post_fallback/:slug/:lang")]
-pub async fn get_post_with_fallback(slug: String, lang: String) -> ServerFnResult<Option<Post>> {
-    Ok(blog::get_post_with_fallback(&slug, &lang))
+pub async fn get_post_with_fallback(
+    slug: String,
+    lang: String,
+) -> ServerFnResult<Option<ServedPost>> {
+    Ok(blog::get_post_with_fallback(&slug, &lang)
+        .map(|served| served.map_post(|post| with_rendered_content(&post))))
+}
+
+#[get("/api/blog/preview/:slug/:lang/:token")]
+pub async fn get_post_preview(
+    slug: String,
+    lang: String,
+    token: String,
+) -> ServerFnResult<Option<Post>> {
+    Ok(blog::get_post_preview(&slug, &lang, &token).map(|post| {
+        let mut post = with_rendered_content(&post);
+        post.content = blog::scope_preview_assets(&post.content, &slug, &lang, &token);
+        post
+    }))
+}
+
+#[get("/api/blog/preview/:slug/:lang/:token/img/:file")]
+pub async fn get_preview_asset(
+    slug: String,
+    lang: String,
+    token: String,
+    file: String,
+) -> ServerFnResult<FileStream> {
+    Ok(blog::get_preview_asset(&slug, &lang, &token, &file).await?)
+}
+
+#[get("/api/blog/post_neighbors/:slug/:lang")]
+pub async fn get_post_neighbors(slug: String, lang: String) -> ServerFnResult<PostNeighbors> {
+    Ok(blog::get_post_neighbors(&slug, &lang))
+}
+
+#[get("/api/blog/related_posts/:slug/:lang")]
+pub async fn get_related_posts(slug: String, lang: String) -> ServerFnResult<Vec<PostMetadata>> {
+    Ok(blog::get_related_posts(
+        &slug,
+        &lang,
+        blog::RELATED_POST_LIMIT,
+    ))
 }
 
 #[get("/api/blog/post_content_fallback/:slug/:lang")]
@@ -27,7 +77,8 @@ pub async fn get_post_content_with_fallback(
     slug: String,
     lang: String,
 ) -> ServerFnResult<Option<String>> {
-    Ok(blog::get_post_content_with_fallback(&slug, &lang))
+    Ok(blog::get_post_with_fallback(&slug, &lang)
+        .map(|served| with_rendered_content(&served.post).content))
 }
 
 #[get("/api/blog/post_languages/:slug")]
@@ -40,11 +91,6 @@ pub async fn get_post_asset(slug: String, file: String) -> ServerFnResult<FileSt
     Ok(blog::get_post_asset(&slug, &file).await?)
 }
 
-#[get("/blog/:slug")]
-pub async fn redirect_blog_post_to_directory(slug: String) -> ServerFnResult<Redirect> {
-    Ok(Redirect::temporary(&format!("/blog/{slug}/")))
-}
-
 #[get("/api/blog/tag_posts/:tag/:lang")]
 pub async fn get_posts_by_tag(tag: String, lang: String) -> ServerFnResult<Vec<PostMetadata>> {
     Ok(blog::get_posts_by_tag_and_lang(&tag, &lang))
@@ -55,14 +101,39 @@ pub async fn get_tag_groups(lang: String) -> ServerFnResult<Vec<TagGroup>> {
     Ok(blog::get_tag_groups(&lang))
 }
 
+/// The query travels as a decoded `?q=` parameter, so it may contain `/`
+/// and other characters a path segment could not.
+#[get("/api/blog/search/:lang?q")]
+pub async fn search_posts(lang: String, q: String) -> ServerFnResult<Vec<SearchHit>> {
+    Ok(search::search_posts(&q, &lang))
+}
+
+#[get("/api/blog/series/:id/:lang")]
+pub async fn get_series(id: String, lang: String) -> ServerFnResult<Option<Series>> {
+    Ok(blog::get_series(&id, &lang))
+}
+
 #[post("/api/static_routes")]
 pub async fn static_routes() -> ServerFnResult<Vec<String>> {
     let mut routes = Route::static_routes()
         .into_iter()
         .map(|route| route.to_string())
+        .filter(|path| !is_localizable_path(path))
         .collect::<Vec<_>>();
+    // Feeds, the sitemap, robots.txt and search indexes are not HTML; the
+    // `export-static` command writes them into the bundle instead.
     routes.extend(blog::get_static_routes());
     routes.sort();
     routes.dedup();
     Ok(routes)
 }
+
+#[cfg(feature = "server")]
+fn with_rendered_content(post: &Post) -> Post {
+    let content = blog::rewrite_post_links(&post.content, &post.meta.slug);
+    let content = prerender_diagrams(&content);
+    Post {
+        meta: post.meta.clone(),
+        content: prerender_math(&content, MathTarget::Component),
+    }
+}
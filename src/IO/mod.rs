@@ -1,3 +1,4 @@
 pub mod about;
 pub mod blog;
+pub mod site;
 pub mod user;
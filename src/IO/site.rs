@@ -0,0 +1,10 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::impls::site;
+use crate::impls::site::SiteInfo;
+
+#[get("/api/site/info")]
+pub async fn get_site_info() -> ServerFnResult<SiteInfo> {
+    Ok(site::site_info())
+}
@@ -7,6 +7,10 @@ use crate::impls::session::AppSession;
 pub struct SessionPreferenceDto {
     pub locale: Option<String>,
     pub theme: Option<String>,
+    /// Last known `prefers-color-scheme`, for rendering the "system" theme.
+    pub system_theme: Option<String>,
+    #[serde(default)]
+    pub fallback_notice_dismissed: bool,
 }
 
 #[get("/api/user/preference", session: AppSession, seed: preference::PreferenceSeed)]
@@ -14,6 +18,8 @@ pub async fn get_preference() -> ServerFnResult<SessionPreferenceDto> {
     Ok(SessionPreferenceDto {
         locale: preference::resolve_locale(&session, &seed),
         theme: preference::resolve_theme(&session),
+        system_theme: preference::system_theme(&seed),
+        fallback_notice_dismissed: preference::fallback_notice_dismissed(&session),
     })
 }
 
@@ -28,3 +34,9 @@ pub async fn set_theme(theme: String) -> ServerFnResult<()> {
     preference::update_theme(&session, &theme);
     Ok(())
 }
+
+#[post("/api/user/fallback_notice/dismiss", session: AppSession)]
+pub async fn dismiss_fallback_notice() -> ServerFnResult<()> {
+    preference::dismiss_fallback_notice(&session);
+    Ok(())
+}
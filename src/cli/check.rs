@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::impls::blog::{check_content, set_content_source, FilesystemSource};
+
+/// `check [--dir <path>]`: validates the content tree and prints one
+/// `file:line: message` per problem. Warnings such as outdated translations
+/// are printed but do not fail the check.
+pub fn run(args: &[String]) -> i32 {
+    match args {
+        [] => {}
+        [flag, dir] if flag == "--dir" => {
+            set_content_source(Arc::new(FilesystemSource::new(dir)));
+        }
+        _ => {
+            eprintln!("usage: check [--dir <path>]");
+            return 2;
+        }
+    }
+
+    let report = check_content();
+    let (warnings, errors): (Vec<_>, Vec<_>) = report
+        .errors
+        .iter()
+        .partition(|error| error.kind.is_warning());
+    for warning in warnings.iter() {
+        eprintln!("warning: {warning}");
+    }
+    for error in errors.iter() {
+        eprintln!("{error}");
+    }
+
+    if errors.is_empty() {
+        println!(
+            "content ok: {} posts checked, {} warning(s)",
+            report.post_count,
+            warnings.len()
+        );
+        0
+    } else {
+        eprintln!(
+            "{} problem(s) and {} warning(s) found in {} posts",
+            errors.len(),
+            warnings.len(),
+            report.post_count
+        );
+        1
+    }
+}
@@ -0,0 +1,24 @@
+use crate::impls::blog::load_content;
+use crate::impls::mermaid::render_diagrams;
+
+/// `render-diagrams`: pre-renders every `<Mermaid>` block to SVG with the
+/// Mermaid CLI so pages can show diagrams before (or without) JavaScript.
+pub fn run() -> i32 {
+    let (posts, _) = load_content();
+    let report = render_diagrams(posts.iter().map(|sourced| sourced.post.content.as_str()));
+    for failure in report.failed.iter() {
+        eprintln!("{failure}");
+    }
+
+    println!(
+        "diagrams: {} rendered, {} already cached, {} failed",
+        report.rendered,
+        report.cached,
+        report.failed.len()
+    );
+    if report.failed.is_empty() {
+        0
+    } else {
+        1
+    }
+}
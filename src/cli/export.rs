@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::impls::blog::get_static_routes;
+use crate::impls::feed::get_feed_documents;
+use crate::impls::i18n;
+use crate::impls::locale_routes::split_locale_prefix;
+use crate::impls::search::get_search_index_documents;
+use crate::impls::sitemap::get_sitemap_documents;
+
+/// `export-static <public dir>`: writes the feeds, `sitemap.xml`,
+/// `robots.txt` and search indexes into a static bundle at the paths the
+/// server answers them on. Static site generation only renders HTML pages,
+/// so without this the bundle would lack them. Fails when any is missing.
+///
+/// The server redirects `/` and unprefixed pages such as `/blog/{slug}/` to a
+/// language; a static host cannot, so each gets a page that redirects in the
+/// browser instead. Pages the bundle already has are left alone.
+pub fn run(args: &[String]) -> i32 {
+    let [dir] = args else {
+        eprintln!("usage: export-static <public dir>");
+        return 2;
+    };
+    let root = Path::new(dir);
+
+    let feeds = get_feed_documents();
+    let search_indexes = get_search_index_documents();
+    if feeds.is_empty() || search_indexes.is_empty() {
+        eprintln!("no published posts; feeds and search indexes were not generated");
+        return 1;
+    }
+
+    let redirects = redirect_documents()
+        .into_iter()
+        .filter(|(path, _)| !root.join(index_file(path)).exists())
+        .map(|(path, body)| (index_file(&path), body));
+    let documents = feeds
+        .into_iter()
+        .chain(get_sitemap_documents())
+        .chain(search_indexes)
+        .chain(redirects)
+        .collect::<Vec<_>>();
+    for (path, body) in documents.iter() {
+        if let Err(error) = write_document(root, path, body) {
+            eprintln!("{path}: {error}");
+            return 1;
+        }
+    }
+
+    println!(
+        "exported {} documents to {}",
+        documents.len(),
+        root.display()
+    );
+    0
+}
+
+fn write_document(root: &Path, path: &str, body: &str) -> std::io::Result<()> {
+    let target = root.join(path.trim_start_matches('/'));
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, body)?;
+    // Read it back so a silently truncated or misplaced write fails the build.
+    if fs::read_to_string(&target)? != body {
+        return Err(std::io::Error::other("written file does not match"));
+    }
+    Ok(())
+}
+
+/// File a static host serves for the page at `path`.
+fn index_file(path: &str) -> String {
+    match path.trim_matches('/') {
+        "" => "index.html".to_string(),
+        dir => format!("{dir}/index.html"),
+    }
+}
+
+/// A redirect page for `/` and every unprefixed page, pointing at the
+/// languages the page exists in.
+fn redirect_documents() -> Vec<(String, String)> {
+    let mut pages = BTreeMap::<String, Vec<&'static str>>::new();
+    for route in get_static_routes() {
+        let Some((lang, rest)) = split_locale_prefix(&route) else {
+            continue;
+        };
+        let Some(locale) = i18n::find_locale(lang) else {
+            continue;
+        };
+        pages.entry(rest.to_string()).or_default().push(locale.code);
+        if rest == "/blog/" {
+            pages.entry("/".to_string()).or_default().push(locale.code);
+        }
+    }
+
+    pages
+        .into_iter()
+        .map(|(path, langs)| {
+            let target = if path == "/" { "/blog/" } else { path.as_str() };
+            let body = redirect_page(target, &langs);
+            (path, body)
+        })
+        .collect()
+}
+
+/// Sends the browser to `/{lang}{target}`, picking the first of its
+/// preferred languages the page exists in and otherwise the site default.
+fn redirect_page(target: &str, langs: &[&str]) -> String {
+    let default = i18n::default_locale().code;
+    let fallback = if langs.contains(&default) {
+        default
+    } else {
+        langs[0]
+    };
+    let fallback_url = format!("/{fallback}{target}");
+    let choices = langs
+        .iter()
+        .filter_map(|code| i18n::find_locale(code))
+        .map(|locale| {
+            (
+                locale.tag().to_lowercase(),
+                format!("/{}{target}", locale.code),
+            )
+        })
+        .collect::<Vec<_>>();
+    let choices = serde_json::to_string(&choices).unwrap_or_else(|_| "[]".to_string());
+    let fallback_json =
+        serde_json::to_string(&fallback_url).unwrap_or_else(|_| "\"/\"".to_string());
+
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="robots" content="noindex">
+<meta http-equiv="refresh" content="0; url={fallback_url}">
+<link rel="canonical" href="{fallback_url}">
+<title>Redirecting…</title>
+<script>
+(function () {{
+  var choices = {choices};
+  var target = {fallback_json};
+  var wanted = navigator.languages || [navigator.language || ""];
+  outer: for (var i = 0; i < wanted.length; i++) {{
+    var tag = String(wanted[i]).toLowerCase();
+    for (var j = 0; j < choices.length; j++) {{
+      if (choices[j][0] === tag || choices[j][0].split("-")[0] === tag.split("-")[0]) {{
+        target = choices[j][1];
+        break outer;
+      }}
+    }}
+  }}
+  location.replace(target + location.search + location.hash);
+}})();
+</script>
+</head>
+<body><a href="{fallback_url}">{fallback_url}</a></body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redirect_pages_live_at_directory_indexes() {
+        assert_eq!(index_file("/"), "index.html");
+        assert_eq!(index_file("/blog/"), "blog/index.html");
+        assert_eq!(index_file("/tags/rust"), "tags/rust/index.html");
+    }
+
+    #[test]
+    fn redirect_pages_fall_back_to_a_language_the_page_exists_in() {
+        let page = redirect_page("/blog/post/", &["en"]);
+        assert!(page.contains(r#"content="0; url=/en/blog/post/""#));
+        assert!(page.contains(r#"[["en-us","/en/blog/post/"]]"#));
+    }
+}
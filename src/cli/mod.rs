@@ -0,0 +1,26 @@
+mod check;
+mod diagrams;
+mod export;
+mod preview;
+mod scaffold;
+
+/// Runs a content subcommand when one is given on the command line. Returns
+/// the process exit code, or `None` to start the web server as usual.
+pub fn run(args: &[String]) -> Option<i32> {
+    let rest = args.get(1..).unwrap_or_default();
+    match args.first().map(String::as_str) {
+        Some("check") => Some(check::run(rest)),
+        Some("new-post") => Some(scaffold::new_post(rest)),
+        Some("translate") => Some(scaffold::translate(rest)),
+        Some("refresh-translation") => Some(scaffold::refresh(rest)),
+        Some("new-tag") => Some(scaffold::new_tag(rest)),
+        Some("render-diagrams") => Some(diagrams::run()),
+        Some("preview-links") => Some(preview::run()),
+        Some("export-static") => Some(export::run(rest)),
+        Some("help" | "--help" | "-h") => {
+            scaffold::print_usage();
+            Some(0)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,19 @@
+use crate::impls::blog::get_preview_links;
+use crate::impls::site::site_url;
+
+/// `preview-links`: prints a signed, expiring preview URL for every draft or
+/// scheduled post. The links are secrets, so they are only ever printed here
+/// and never logged by the server.
+pub fn run() -> i32 {
+    let links = get_preview_links();
+    if links.is_empty() {
+        eprintln!("no unpublished posts, or PREVIEW_SECRET is not set");
+        return 0;
+    }
+
+    let site = site_url();
+    for (meta, path) in links {
+        println!("{} ({}): {site}{path}", meta.slug, meta.lang);
+    }
+    0
+}
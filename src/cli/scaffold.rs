@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+
+use crate::impls::blog::{
+    create_post, create_tag, create_translation, refresh_translation, NewPost,
+};
+use crate::impls::site::site_config;
+
+/// `new-post <slug> [--lang <default locale>] [--title "..."] [--tags a,b]`
+pub fn new_post(args: &[String]) -> i32 {
+    let (positional, options) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(error) => return usage(&error),
+    };
+    let [slug] = positional.as_slice() else {
+        return usage("new-post expects exactly one <slug>");
+    };
+
+    let request = NewPost {
+        slug: slug.clone(),
+        lang: option(&options, "lang")
+            .unwrap_or(&site_config().info.default_locale)
+            .to_string(),
+        title: option(&options, "title").map(str::to_string),
+        tags: option(&options, "tags")
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    match create_post(request) {
+        Ok(paths) => {
+            for path in paths {
+                println!("created {}", path.display());
+            }
+            0
+        }
+        Err(error) => fail(&error),
+    }
+}
+
+/// `translate <slug> <lang> [--from cn]`
+pub fn translate(args: &[String]) -> i32 {
+    let (positional, options) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(error) => return usage(&error),
+    };
+    let [slug, lang] = positional.as_slice() else {
+        return usage("translate expects <slug> <lang>");
+    };
+
+    match create_translation(slug, lang, option(&options, "from")) {
+        Ok(path) => {
+            println!("created {} (marked untranslated)", path.display());
+            0
+        }
+        Err(error) => fail(&error),
+    }
+}
+
+/// `refresh-translation <slug> <lang>`
+pub fn refresh(args: &[String]) -> i32 {
+    let [slug, lang] = args else {
+        return usage("refresh-translation expects <slug> <lang>");
+    };
+
+    match refresh_translation(slug, lang) {
+        Ok(Some(path)) => {
+            println!("updated translated_from.hash in {}", path.display());
+            0
+        }
+        Ok(None) => {
+            println!("{slug} ({lang}) is already up to date");
+            0
+        }
+        Err(error) => fail(&error),
+    }
+}
+
+/// `new-tag <id> [--label cn=标签] [--label en=Label]`
+pub fn new_tag(args: &[String]) -> i32 {
+    let (positional, options) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(error) => return usage(&error),
+    };
+    let [id] = positional.as_slice() else {
+        return usage("new-tag expects exactly one <id>");
+    };
+
+    let mut labels = BTreeMap::new();
+    for (key, value) in options.iter().filter(|(key, _)| key == "label") {
+        let Some((lang, label)) = value.split_once('=') else {
+            return usage(&format!("--{key} expects <lang>=<label>, got '{value}'"));
+        };
+        labels.insert(lang.trim().to_string(), label.trim().to_string());
+    }
+
+    match create_tag(id, labels) {
+        Ok(path) => {
+            println!("added tag '{id}' to {}", path.display());
+            0
+        }
+        Err(error) => fail(&error),
+    }
+}
+
+pub fn print_usage() {
+    eprintln!(
+        "usage:
+  check [--dir <path>]
+  new-post <slug> [--lang <lang>] [--title <title>] [--tags <a,b>]
+  translate <slug> <lang> [--from <lang>]
+  refresh-translation <slug> <lang>
+  new-tag <id> [--label <lang>=<label>]...
+  render-diagrams
+  preview-links
+  export-static <public dir>"
+    );
+}
+
+type Options = Vec<(String, String)>;
+
+fn parse_args(args: &[String]) -> Result<(Vec<String>, Options), String> {
+    let mut positional = Vec::new();
+    let mut options = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            positional.push(arg.clone());
+            continue;
+        };
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name.to_string(), value.to_string()),
+            None => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("--{name} expects a value"))?;
+                (name.to_string(), value.clone())
+            }
+        };
+        options.push((name, value));
+    }
+    Ok((positional, options))
+}
+
+fn option<'a>(options: &'a Options, name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .rev()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn usage(error: &str) -> i32 {
+    eprintln!("error: {error}");
+    print_usage();
+    2
+}
+
+fn fail(error: &str) -> i32 {
+    eprintln!("error: {error}");
+    1
+}
@@ -30,8 +30,8 @@ fn tags_crumb() -> Crumb {
 
 fn build_crumbs(route: &Route) -> Vec<Crumb> {
     match route {
-        Route::BlogList {} => vec![home_crumb()],
-        Route::BlogPost { slug } => vec![
+        Route::BlogList {} | Route::LocalizedBlogList { .. } => vec![home_crumb()],
+        Route::BlogPost { slug } | Route::LocalizedBlogPost { slug, .. } => vec![
             home_crumb(),
             blog_crumb(),
             Crumb {
@@ -39,14 +39,14 @@ fn build_crumbs(route: &Route) -> Vec<Crumb> {
                 to: None,
             },
         ],
-        Route::TagList {} => vec![
+        Route::TagList {} | Route::LocalizedTagList { .. } => vec![
             home_crumb(),
             Crumb {
                 label: "tags".to_string(),
                 to: None,
             },
         ],
-        Route::TagsTag { tag } => vec![
+        Route::TagsTag { tag } | Route::LocalizedTagsTag { tag, .. } => vec![
             home_crumb(),
             tags_crumb(),
             Crumb {
@@ -54,6 +54,17 @@ fn build_crumbs(route: &Route) -> Vec<Crumb> {
                 to: None,
             },
         ],
+        Route::SeriesDetail { id } | Route::LocalizedSeriesDetail { id, .. } => vec![
+            home_crumb(),
+            Crumb {
+                label: "series".to_string(),
+                to: None,
+            },
+            Crumb {
+                label: id.clone(),
+                to: None,
+            },
+        ],
         Route::About {} => vec![
             home_crumb(),
             Crumb {
@@ -61,6 +72,24 @@ fn build_crumbs(route: &Route) -> Vec<Crumb> {
                 to: None,
             },
         ],
+        Route::PostPreview { slug, .. } => vec![
+            home_crumb(),
+            Crumb {
+                label: "preview".to_string(),
+                to: None,
+            },
+            Crumb {
+                label: slug.clone(),
+                to: None,
+            },
+        ],
+        Route::Search { .. } => vec![
+            home_crumb(),
+            Crumb {
+                label: "search".to_string(),
+                to: None,
+            },
+        ],
         Route::PageNotFound { .. } => vec![
             home_crumb(),
             Crumb {
@@ -80,6 +109,7 @@ pub fn CommonBreadcrumb(
     let dim_opacity_class = "opacity-50";
     let route = use_route::<Route>();
     let crumbs = build_crumbs(&route);
+    let route_locale = route.locale().map(str::to_string);
     let total = crumbs.len();
 
     rsx! {
@@ -106,7 +136,7 @@ pub fn CommonBreadcrumb(
                             }
                         } else if let Some(to) = crumb.to {
                             Link {
-                                to: to,
+                                to: to.localized(route_locale.as_deref()),
                                 class: "transition-colors transition-opacity duration-200 {dim_opacity_class} hover:text-foreground hover:opacity-100 hover:underline underline-offset-4",
                                 "{crumb.label}"
                             }
@@ -1,7 +1,9 @@
 use crate::components::icons::I18NIcon;
 use crate::components::providers::preference_provider::{
-    locale_to_langid, resolve_locale, PreferenceContext, PreferenceStoreStoreExt,
+    PreferenceContext, PreferenceStoreStoreExt,
 };
+use crate::impls::i18n as app_i18n;
+use crate::root::Route;
 use crate::IO::user;
 use dioxus::prelude::*;
 use dioxus_i18n::prelude::*;
@@ -9,35 +11,45 @@ use dioxus_use_js::use_js;
 
 use_js!("src/js/theme_bridge.js"::js_apply_lang);
 
+/// Cycles through the enabled locales in the order `site.toml` lists them.
 #[component]
 pub fn LocaleSwitcher(#[props(default = false)] compact: bool) -> Element {
     let preference = use_context::<PreferenceContext>();
     let mut locale = preference.locale();
-    let current_locale = resolve_locale(locale.read().as_deref());
+    let current = app_i18n::resolve_locale(locale.read().as_deref());
+    let current_locale = current.code;
+    let enabled = app_i18n::enabled_locales();
+    let next = enabled
+        .iter()
+        .position(|locale| locale.code == current_locale)
+        .and_then(|index| enabled.get((index + 1) % enabled.len()))
+        .copied()
+        .unwrap_or(current);
+    let title_for_button = format!("Switch to {}", next.name);
     let mut i18n = i18n();
-    let (locale_to_set_on_click, title_for_button) = if current_locale == "en" {
-        ("cn", "Switch to Chinese")
-    } else {
-        ("en", "Switch to English")
-    };
+    let route = use_route::<Route>();
+    let navigator = use_navigator();
     use_effect(move || {
-        let locale = current_locale.to_string();
+        let tag = current.tag();
         spawn(async move {
-            let _ = js_apply_lang::<()>(locale).await;
+            let _ = js_apply_lang::<()>(tag).await;
         });
     });
 
     let handle_locale_toggle = move |_| {
-        locale.set(Some(locale_to_set_on_click.to_string()));
-        i18n.set_language(locale_to_langid(Some(locale_to_set_on_click)));
-        let next_locale = locale_to_set_on_click.to_string();
+        locale.set(Some(next.code.to_string()));
+        i18n.set_language(next.langid.clone());
+        let next_tag = next.tag();
         spawn(async move {
-            let _ = js_apply_lang::<()>(next_locale).await;
+            let _ = js_apply_lang::<()>(next_tag).await;
         });
-        let locale_str = locale_to_set_on_click;
-        log::info!("Locale changed to: {}", locale_str);
+        log::info!("Locale changed to: {}", next.code);
+        // Content pages take their language from the URL, not the preference.
+        if route.locale().is_some() {
+            navigator.push(route.clone().localized(Some(next.code)));
+        }
         spawn(async move {
-            if let Err(e) = user::set_locale(locale_str.to_string()).await {
+            if let Err(e) = user::set_locale(next.code.to_string()).await {
                 log::warn!("Failed to save locale to session: {e}");
             }
         });
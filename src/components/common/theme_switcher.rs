@@ -1,15 +1,14 @@
 use crate::IO::user;
 use crate::{
-    components::icons::{MoonIcon, SunIcon},
+    components::icons::{MonitorIcon, MoonIcon, SunIcon},
     components::providers::preference_provider::{
-        resolve_theme, PreferenceContext, PreferenceStoreStoreExt,
+        resolve_theme, resolve_theme_mode, PreferenceContext, PreferenceStoreStoreExt,
     },
+    impls::theme::{self as app_theme, Appearance, SYSTEM_THEME},
 };
 use dioxus::prelude::*;
-use dioxus_use_js::use_js;
-
-use_js!("src/js/theme_bridge.js"::js_apply_theme);
 
+/// Lists "System" and every registered theme in a menu above the button.
 #[component]
 pub fn ThemeSwitcher(
     #[props(default = false)] is_mobile: bool,
@@ -17,61 +16,73 @@ pub fn ThemeSwitcher(
 ) -> Element {
     let preference = use_context::<PreferenceContext>();
     let mut theme = preference.theme();
+    let system_theme = preference.system_theme();
+    let mut is_open = use_signal(|| false);
 
-    let handle_theme_toggle = move |_| {
-        let current_theme = resolve_theme(theme.read().as_deref());
-        let new_theme = match current_theme {
-            "light" => "dark",
-            _ => "light",
-        };
-        theme.set(Some(new_theme.to_string()));
-        let next_theme = new_theme.to_string();
-        spawn(async move {
-            let _ = js_apply_theme::<()>(next_theme).await;
-        });
-        let theme_str = new_theme;
+    let current_mode = resolve_theme_mode(theme.read().as_deref());
+    let current_theme = resolve_theme(theme.read().as_deref(), system_theme.read().as_deref());
 
+    // PreferenceProvider applies the new theme to the document.
+    let mut select_theme = move |next: &'static str| {
+        is_open.set(false);
+        theme.set(Some(next.to_string()));
         spawn(async move {
-            if let Err(e) = user::set_theme(theme_str.to_string()).await {
+            if let Err(e) = user::set_theme(next.to_string()).await {
                 log::warn!("Failed to save theme to session: {e}");
             }
         });
     };
 
-    let current_theme = resolve_theme(theme.read().as_deref());
-
-    use_effect(move || {
-        let theme = current_theme.to_string();
-        spawn(async move {
-            let _ = js_apply_theme::<()>(theme).await;
-        });
-    });
-    let title_text = match current_theme {
-        "light" => "Switch to dark mode",
-        _ => "Switch to light mode",
-    };
+    let options = std::iter::once((SYSTEM_THEME, "System"))
+        .chain(app_theme::themes().iter().map(|def| (def.id, def.name)))
+        .collect::<Vec<_>>();
+    let title_text = format!("Theme: {}", current_theme.name);
     let button_class = if compact {
         "w-5 h-5 flex items-center justify-center focus:outline-none cursor-pointer text-muted-foreground opacity-50 hover:text-foreground hover:opacity-100 transition-colors transition-opacity duration-200"
     } else {
         "w-8 h-8 flex items-center justify-center rounded focus:outline-none cursor-pointer text-muted-foreground opacity-50 hover:text-foreground hover:opacity-100 transition-colors transition-opacity duration-200"
     };
+    let icon_class = if compact { "w-3.5 h-3.5" } else { "w-4 h-4" };
 
     rsx! {
         div {
-            class: "flex items-center justify-center",
+            class: "relative flex items-center justify-center",
             button {
                 class: "{button_class}",
                 title: "{title_text}",
-                onclick: handle_theme_toggle,
-                if compact {
-                    match current_theme {
-                        "light" => rsx! { MoonIcon { class: "w-3.5 h-3.5" } },
-                        _ => rsx! { SunIcon { class: "w-3.5 h-3.5" } },
-                    }
+                aria_haspopup: "menu",
+                aria_expanded: "{is_open()}",
+                onclick: move |_| is_open.set(!is_open()),
+                if current_mode == SYSTEM_THEME {
+                    MonitorIcon { class: icon_class }
+                } else if current_theme.appearance == Appearance::Dark {
+                    MoonIcon { class: icon_class }
                 } else {
-                    match current_theme {
-                        "light" => rsx! { MoonIcon { class: "w-4 h-4" } },
-                        _ => rsx! { SunIcon { class: "w-4 h-4" } },
+                    SunIcon { class: icon_class }
+                }
+            }
+            if is_open() {
+                div {
+                    class: "fixed inset-0 z-30",
+                    onclick: move |_| is_open.set(false),
+                }
+                ul {
+                    class: "absolute bottom-full right-0 z-40 mb-2 min-w-36 py-1 rounded-md border border-border bg-popover text-popover-foreground font-mono text-xs",
+                    role: "menu",
+                    for (id, name) in options {
+                        li { key: "{id}",
+                            button {
+                                class: if id == current_mode {
+                                    "w-full px-3 py-1.5 text-left cursor-pointer text-foreground"
+                                } else {
+                                    "w-full px-3 py-1.5 text-left cursor-pointer text-muted-foreground hover:text-foreground transition-colors duration-200"
+                                },
+                                role: "menuitemradio",
+                                aria_checked: "{id == current_mode}",
+                                onclick: move |_| select_theme(id),
+                                "{name}"
+                            }
+                        }
                     }
                 }
             }
@@ -4,6 +4,7 @@ pub mod i18n;
 pub mod linkedin;
 pub mod logo;
 pub mod menu;
+pub mod monitor;
 pub mod moon;
 pub mod sun;
 pub mod twitter;
@@ -15,6 +16,7 @@ pub use i18n::I18NIcon;
 pub use linkedin::LinkedinIcon;
 pub use logo::LogoIcon;
 pub use menu::MenuIcon;
+pub use monitor::MonitorIcon;
 pub use moon::MoonIcon;
 pub use sun::SunIcon;
 pub use twitter::TwitterIcon;
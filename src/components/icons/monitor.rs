@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+#[component]
+pub fn MonitorIcon(#[props(extends = GlobalAttributes)] attributes: Vec<Attribute>) -> Element {
+    rsx!(
+        svg {
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            ..attributes,
+            path {
+                fill: "none",
+                stroke: "currentColor",
+                stroke_linecap: "round",
+                stroke_linejoin: "round",
+                stroke_width: "1",
+                d: "M4 5h16v11H4zM9 20h6M12 16v4"
+            }
+        }
+    )
+}
@@ -0,0 +1,28 @@
+use crate::utils::url::percent_decode;
+use dioxus::prelude::*;
+use dioxus_markdown::CustomComponents;
+
+/// Displays MathML rendered on the server by `impls::math::prerender_math`.
+#[component]
+pub fn MathComponent(mathml: String, display: Option<String>) -> Element {
+    let mathml = percent_decode(&mathml);
+    if display.as_deref() == Some("block") {
+        rsx! {
+            div { class: "my-4 overflow-x-auto math-block", dangerous_inner_html: "{mathml}" }
+        }
+    } else {
+        rsx! {
+            span { class: "math-inline", dangerous_inner_html: "{mathml}" }
+        }
+    }
+}
+
+pub fn registe_md_comp(components: &mut CustomComponents) {
+    components.register("Math", |props| {
+        let mathml = props.get("mathml").unwrap_or_default();
+        let display = props.get("display");
+        Ok(rsx! {
+            MathComponent { mathml, display }
+        })
+    });
+}
@@ -1,10 +1,17 @@
+use crate::components::providers::preference_provider::use_resolved_theme;
+use crate::impls::site::SiteInfo;
+use crate::utils::url::percent_decode;
 use dioxus::prelude::*;
 use dioxus_markdown::CustomComponents;
 use dioxus_use_js::use_js;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use_js!("src/js/mermaid_bridge.js"::js_render_mermaid_by_base_id);
 
+/// Pinned copy of Mermaid, fetched by `scripts/vendor-mermaid.sh`.
+const MERMAID_JS: Asset = asset!("/assets/vendor/mermaid/mermaid.min.js");
+
 static MERMAID_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
 
 fn next_mermaid_base_id() -> String {
@@ -14,11 +21,41 @@ fn next_mermaid_base_id() -> String {
     )
 }
 
+/// Loads Mermaid for a page that renders diagrams. `mermaid_url` in the site
+/// configuration replaces the bundled copy.
+#[component]
+pub fn MermaidScript() -> Element {
+    let site_info = use_context::<SiteInfo>();
+    let src = site_info
+        .mermaid_url
+        .clone()
+        .unwrap_or_else(|| MERMAID_JS.to_string());
+
+    rsx! {
+        document::Script { src }
+    }
+}
+
+/// `source` and `svgs` are set by `impls::mermaid::prerender_diagrams` when
+/// pre-rendered SVGs exist; the one for the current theme is shown until
+/// Mermaid re-renders the diagram, or for good when scripts do not run.
 #[component]
-pub fn MermaidComponent(content: Option<String>, children: Element) -> Element {
+pub fn MermaidComponent(
+    content: Option<String>,
+    source: Option<String>,
+    svgs: Option<String>,
+    children: Element,
+) -> Element {
     let base_id = use_hook(next_mermaid_base_id);
     let source_id = format!("{base_id}-src");
     let output_id = format!("{base_id}-out");
+    let definition = source.as_deref().map(percent_decode).or(content);
+    let mermaid_theme = use_resolved_theme().mermaid_theme;
+    let svg = svgs
+        .as_deref()
+        .map(percent_decode)
+        .and_then(|svgs| serde_json::from_str::<HashMap<String, String>>(&svgs).ok())
+        .and_then(|mut svgs| svgs.remove(mermaid_theme));
     let mut mounted = use_signal(|| false);
 
     use_effect(move || {
@@ -26,7 +63,14 @@ pub fn MermaidComponent(content: Option<String>, children: Element) -> Element {
     });
 
     if !mounted() {
-        return rsx! {};
+        return match svg {
+            Some(svg) => rsx! {
+                div { class: "my-4 mermaid-block",
+                    div { class: "mermaid-output", dangerous_inner_html: "{svg}" }
+                }
+            },
+            None => rsx! {},
+        };
     }
 
     use_effect(move || {
@@ -41,13 +85,17 @@ pub fn MermaidComponent(content: Option<String>, children: Element) -> Element {
             div {
                 id: "{source_id}",
                 style: "display:none;",
-                if let Some(ref content) = content {
-                    "{content}"
+                if let Some(ref definition) = definition {
+                    "{definition}"
                 } else {
                     {children}
                 }
             }
-            div { id: "{output_id}", class: "mermaid-output" }
+            div {
+                id: "{output_id}",
+                class: "mermaid-output",
+                dangerous_inner_html: svg.unwrap_or_default(),
+            }
         }
     }
 }
@@ -55,9 +103,11 @@ pub fn MermaidComponent(content: Option<String>, children: Element) -> Element {
 pub fn registe_md_comp(components: &mut CustomComponents) {
     components.register("Mermaid", |props| {
         let content = props.get("content");
+        let source = props.get("source");
+        let svgs = props.get("svgs");
         let children = props.children;
         Ok(rsx! {
-            MermaidComponent { content, children }
+            MermaidComponent { content, source, svgs, children }
         })
     });
 }
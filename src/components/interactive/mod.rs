@@ -1,6 +1,8 @@
 pub mod code_runner;
 pub mod color_picker;
 pub mod counter;
+pub mod math;
 pub mod mermaid;
+pub mod post_image;
 pub mod social_link;
 pub mod test;
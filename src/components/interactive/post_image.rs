@@ -0,0 +1,77 @@
+use crate::utils::url::percent_decode;
+use dioxus::prelude::*;
+use dioxus_markdown::CustomComponents;
+
+/// Post image emitted by `impls::blog::rewrite_post_links`; width/height are
+/// the intrinsic size so the browser can reserve space before loading. Raster
+/// images also carry resized AVIF/WebP `srcset`s and render as `<picture>`.
+#[component]
+pub fn PostImageComponent(
+    src: String,
+    alt: String,
+    title: Option<String>,
+    width: Option<String>,
+    height: Option<String>,
+    srcset: Option<String>,
+    webp_srcset: Option<String>,
+    avif_srcset: Option<String>,
+    sizes: Option<String>,
+) -> Element {
+    let image = rsx! {
+        img {
+            src: "{src}",
+            alt: "{alt}",
+            title,
+            width,
+            height,
+            srcset,
+            sizes: sizes.clone(),
+            loading: "lazy",
+            decoding: "async",
+        }
+    };
+
+    if avif_srcset.is_none() && webp_srcset.is_none() {
+        return image;
+    }
+
+    rsx! {
+        picture {
+            if let Some(avif_srcset) = avif_srcset {
+                source { r#type: "image/avif", srcset: "{avif_srcset}", sizes: sizes.clone() }
+            }
+            if let Some(webp_srcset) = webp_srcset {
+                source { r#type: "image/webp", srcset: "{webp_srcset}", sizes: sizes.clone() }
+            }
+            {image}
+        }
+    }
+}
+
+pub fn registe_md_comp(components: &mut CustomComponents) {
+    components.register("PostImage", |props| {
+        let decoded = |name: &str| props.get(name).map(|value| percent_decode(&value));
+        let src = decoded("src").unwrap_or_default();
+        let alt = decoded("alt").unwrap_or_default();
+        let title = decoded("title");
+        let width = decoded("width");
+        let height = decoded("height");
+        let srcset = decoded("srcset");
+        let webp_srcset = decoded("webp_srcset");
+        let avif_srcset = decoded("avif_srcset");
+        let sizes = decoded("sizes");
+        Ok(rsx! {
+            PostImageComponent {
+                src,
+                alt,
+                title,
+                width,
+                height,
+                srcset,
+                webp_srcset,
+                avif_srcset,
+                sizes,
+            }
+        })
+    });
+}
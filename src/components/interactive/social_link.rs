@@ -1,33 +1,35 @@
 use crate::components::icons::{GithubIcon, LinkedinIcon, TwitterIcon};
+use crate::impls::site::{SiteInfo, SocialKind};
 use dioxus::prelude::*;
 use dioxus_markdown::CustomComponents;
 
 #[component]
 pub fn SocialLinkComponent() -> Element {
+    let site_info = use_context::<SiteInfo>();
+
     rsx! {
         div { class: "flex items-center gap-3",
-            Link {
-                to: "https://github.com/zhiyanzhaijie",
-                rel: "noopener noreferrer",
-                class: "text-muted-foreground hover:text-foreground transition-colors",
-                GithubIcon { class: "w-5 h-5" }
-            }
-            Link {
-                to: "https://www.linkedin.com/in/corwin-chan-1a575b400",
-                rel: "noopener noreferrer",
-                class: "text-muted-foreground hover:text-foreground transition-colors",
-                LinkedinIcon { class: "w-5 h-5" }
-            }
-            Link {
-                to: "https://x.com/zyzj8",
-                rel: "noopener noreferrer",
-                class: "text-muted-foreground hover:text-foreground transition-colors",
-                TwitterIcon { class: "w-5 h-5" }
+            for link in site_info.social_links.iter() {
+                Link {
+                    key: "{link.url}",
+                    to: link.url.clone(),
+                    rel: "noopener noreferrer",
+                    class: "text-muted-foreground hover:text-foreground transition-colors",
+                    {social_icon(link.kind)}
+                }
             }
         }
     }
 }
 
+fn social_icon(kind: SocialKind) -> Element {
+    match kind {
+        SocialKind::Github => rsx! { GithubIcon { class: "w-5 h-5" } },
+        SocialKind::Linkedin => rsx! { LinkedinIcon { class: "w-5 h-5" } },
+        SocialKind::Twitter => rsx! { TwitterIcon { class: "w-5 h-5" } },
+    }
+}
+
 pub fn registe_md_comp(components: &mut CustomComponents) {
     components.register("SocialLink", |_props| {
         Ok(rsx! {
@@ -39,4 +41,4 @@ pub fn registe_md_comp(components: &mut CustomComponents) {
             SocialLinkComponent {}
         })
     });
-}
\ No newline at end of file
+}
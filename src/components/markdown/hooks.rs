@@ -1,4 +1,6 @@
-use crate::components::interactive::{code_runner, color_picker, counter, mermaid, social_link, test};
+use crate::components::interactive::{
+    code_runner, color_picker, counter, math, mermaid, post_image, social_link, test,
+};
 use dioxus::prelude::*;
 use dioxus_markdown::CustomComponents;
 
@@ -9,7 +11,9 @@ pub fn use_markdown_components() -> ReadSignal<CustomComponents> {
         test::registe_md_comp(&mut components);
         color_picker::registe_md_comp(&mut components);
         code_runner::registe_md_comp(&mut components);
+        math::registe_md_comp(&mut components);
         mermaid::registe_md_comp(&mut components);
+        post_image::registe_md_comp(&mut components);
         social_link::registe_md_comp(&mut components);
         Signal::new(components).into()
     })
@@ -1,17 +1,10 @@
-use crate::components::providers::preference_provider::{
-    resolve_theme, PreferenceContext, PreferenceStoreStoreExt,
-};
+use crate::components::providers::preference_provider::use_resolved_theme;
 use dioxus::prelude::*;
 use dioxus_markdown::{CustomComponents, Markdown};
 
 #[component]
 pub fn MarkdownRenderer(content: String, components: ReadSignal<CustomComponents>) -> Element {
-    let preference = use_context::<PreferenceContext>();
-    let theme_pref = preference.theme();
-    let theme = match resolve_theme(theme_pref.read().as_deref()) {
-        "dark" => Some("base16-ocean.dark"),
-        _ => Some("base16-ocean.light"),
-    };
+    let theme = Some(use_resolved_theme().syntax_theme);
 
     rsx! {
         div { class: "markdown-body",
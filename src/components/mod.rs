@@ -5,4 +5,5 @@ pub mod interactive;
 pub mod layout;
 pub mod markdown;
 pub mod providers;
+pub mod search;
 pub mod views;
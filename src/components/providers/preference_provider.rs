@@ -1,11 +1,21 @@
 use dioxus::prelude::*;
-use unic_langid::{langid, LanguageIdentifier};
+use dioxus_use_js::use_js;
 
+use crate::impls::i18n;
+use crate::impls::theme::{self as app_theme, ThemeDef, SYSTEM_THEME};
+use crate::root::Route;
 use crate::IO::user::SessionPreferenceDto;
+
+use_js!("src/js/theme_bridge.js"::{js_apply_theme, js_system_theme, js_next_system_theme});
+
 #[derive(Clone, Store)]
 pub struct PreferenceStore {
     pub locale: Option<String>,
     pub theme: Option<String>,
+    /// `prefers-color-scheme` as "light" or "dark"; only used by the
+    /// "system" theme.
+    pub system_theme: Option<String>,
+    pub fallback_notice_dismissed: bool,
 }
 
 impl From<SessionPreferenceDto> for PreferenceStore {
@@ -13,6 +23,8 @@ impl From<SessionPreferenceDto> for PreferenceStore {
         Self {
             locale: value.locale,
             theme: value.theme,
+            system_theme: value.system_theme,
+            fallback_notice_dismissed: value.fallback_notice_dismissed,
         }
     }
 }
@@ -23,27 +35,73 @@ pub type PreferenceContext = Store<PreferenceStore>;
 pub fn PreferenceProvider(initial: SessionPreferenceDto, children: Element) -> Element {
     let preference: PreferenceContext = use_store(|| initial.clone().into());
     use_context_provider(|| preference);
+    let mut system_theme_ready = use_signal(|| false);
+
+    // The server only knows the color scheme from the last visit's cookie, so
+    // ask the browser once mounted and keep following it.
+    use_effect(move || {
+        spawn(async move {
+            let mut system_theme = preference.system_theme();
+            if let Ok(theme) = js_system_theme::<String>().await {
+                system_theme.set(Some(theme));
+            }
+            system_theme_ready.set(true);
+            while let Ok(theme) = js_next_system_theme::<String>().await {
+                system_theme.set(Some(theme));
+            }
+        });
+    });
+
+    use_effect(move || {
+        let mode = resolve_theme_mode(preference.theme().read().as_deref());
+        // Until the browser has answered, the bootstrap script's class is the
+        // correct one and the server's guess must not overwrite it.
+        if mode == SYSTEM_THEME && !system_theme_ready() {
+            return;
+        }
+        let theme = resolve_theme(
+            preference.theme().read().as_deref(),
+            preference.system_theme().read().as_deref(),
+        );
+        spawn(async move {
+            let _ = js_apply_theme::<()>(theme.id, theme.appearance.as_str(), theme.mermaid_theme)
+                .await;
+        });
+    });
 
     children
 }
 
 pub fn resolve_locale(locale: Option<&str>) -> &'static str {
-    match locale {
-        Some("en") => "en",
-        _ => "cn",
-    }
+    i18n::resolve_locale(locale).code
 }
 
-pub fn resolve_theme(theme: Option<&str>) -> &'static str {
-    match theme {
-        Some("dark") => "dark",
-        _ => "light",
-    }
+/// Language pinned by a `/{lang}/...` URL. It decides which content is shown
+/// and which language links on the page point to.
+pub fn use_route_locale() -> Option<String> {
+    use_route::<Route>().locale().map(str::to_string)
 }
 
-pub fn locale_to_langid(locale: Option<&str>) -> LanguageIdentifier {
-    match resolve_locale(locale) {
-        "en" => langid!("en-US"),
-        _ => langid!("zh-CN"),
-    }
+pub fn is_site_locale(lang: &str) -> bool {
+    i18n::enabled_locales()
+        .iter()
+        .any(|locale| locale.code == lang)
+}
+
+/// The stored theme preference: a theme id or "system".
+pub fn resolve_theme_mode(theme: Option<&str>) -> &'static str {
+    app_theme::resolve_theme_preference(theme)
+}
+
+/// The theme actually rendered. The "system" preference follows
+/// `system_theme`.
+pub fn resolve_theme(theme: Option<&str>, system_theme: Option<&str>) -> &'static ThemeDef {
+    app_theme::resolve_theme(theme, system_theme)
+}
+
+pub fn use_resolved_theme() -> &'static ThemeDef {
+    let preference = use_context::<PreferenceContext>();
+    let theme = preference.theme().cloned();
+    let system_theme = preference.system_theme().cloned();
+    resolve_theme(theme.as_deref(), system_theme.as_deref())
 }
@@ -0,0 +1,37 @@
+use crate::impls::search::{SearchHit, SearchIndex, SEARCH_RESULT_LIMIT};
+use dioxus_use_js::use_js;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use_js!("src/js/search_bridge.js"::js_fetch_text);
+
+thread_local! {
+    static SEARCH_INDEXES: RefCell<HashMap<String, Rc<SearchIndex>>> = RefCell::new(HashMap::new());
+}
+
+pub async fn load_search_index(lang: &str) -> Option<Rc<SearchIndex>> {
+    if let Some(index) = SEARCH_INDEXES.with(|indexes| indexes.borrow().get(lang).cloned()) {
+        return Some(index);
+    }
+
+    let text = js_fetch_text::<Option<String>>(SearchIndex::asset_path(lang))
+        .await
+        .ok()
+        .flatten()?;
+    let index = match serde_json::from_str::<SearchIndex>(&text) {
+        Ok(index) => Rc::new(index),
+        Err(error) => {
+            log::warn!("Failed to parse search index for {lang}: {error}");
+            return None;
+        }
+    };
+
+    SEARCH_INDEXES.with(|indexes| indexes.borrow_mut().insert(lang.to_string(), index.clone()));
+    Some(index)
+}
+
+pub async fn search_offline(lang: &str, query: &str) -> Option<Vec<SearchHit>> {
+    let index = load_search_index(lang).await?;
+    Some(index.search(query, SEARCH_RESULT_LIMIT))
+}
@@ -0,0 +1,133 @@
+use crate::components::common::layout_cell::{LayoutCell, LayoutCellPadding};
+use crate::components::interactive::mermaid::MermaidScript;
+use crate::components::layout::root::toc::RootContentToc;
+use crate::components::markdown::hooks::use_markdown_components;
+use crate::components::markdown::renderer::MarkdownRenderer;
+use crate::components::providers::interactive_provider::InteractiveContext;
+use crate::components::providers::preference_provider::use_route_locale;
+use crate::components::views::series::nav::SeriesNav;
+use crate::impls::blog::Post;
+use crate::impls::i18n;
+use crate::root::Route;
+use crate::utils::markdown_toc::inject_heading_anchors_and_collect_toc;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+#[component]
+pub fn BlogArticle(post: Post, notice: Option<Element>, footer: Option<Element>) -> Element {
+    let route_locale = use_route_locale();
+    let markdown_components = use_markdown_components();
+    let mut interactive_context = use_context::<InteractiveContext>();
+    let meta = post.meta;
+    let markdown_key = format!("{}-{}", meta.slug, meta.lang);
+    let (content_with_anchors, toc_items) = inject_heading_anchors_and_collect_toc(&post.content);
+    let outdated_source = meta.outdated_from.clone().map(|lang| {
+        let name =
+            i18n::find_locale(&lang).map_or_else(|| lang.clone(), |locale| locale.name.to_string());
+        (lang, name)
+    });
+
+    rsx! {
+        LayoutCell {
+            padding: LayoutCellPadding::Normal,
+            div {
+                onmouseenter: move |_| interactive_context.post_focus.set(true),
+                onmouseleave: move |_| interactive_context.post_focus.set(false),
+
+                if meta.has_mermaid {
+                    MermaidScript {}
+                }
+
+                if let Some(notice) = notice {
+                    {notice}
+                }
+
+                if let Some((source_lang, source_name)) = outdated_source {
+                    div {
+                        role: "note",
+                        class: "mb-6 py-2 border-y border-zinc-200 dark:border-zinc-800 font-mono text-xs text-muted-foreground",
+                        {t!("blog_article_outdated_notice", source: source_name)}
+                        " • "
+                        Link {
+                            to: Route::LocalizedBlogPost { lang: source_lang, slug: meta.slug.clone() },
+                            class: "hover:text-foreground hover:underline underline-offset-4 transition-colors",
+                            {t!("blog_article_outdated_original")}
+                        }
+                    }
+                }
+
+                article {
+                    header { class: "mb-7",
+                        h1 { class: "text-xl sm:text-2xl font-semibold tracking-tight text-foreground mb-2 leading-tight", {meta.title.clone()} }
+
+
+                        div { class: "flex flex-col sm:flex-row sm:items-center gap-2 sm:gap-4 text-sm text-muted-foreground",
+                            p { class: "flex items-center",
+                                "Published on {meta.date.clone()}"
+                            }
+
+                            span { class: "hidden sm:inline-block text-xs",
+                                "•"
+                            }
+                            span { class: "hidden sm:inline-block text-xs",
+                                "{meta.word_count} words"
+                            }
+                            if meta.untranslated {
+                                span { class: "hidden sm:inline-block text-xs",
+                                    "•"
+                                }
+                                span { class: "text-xs",
+                                    {t!("blog_article_translation_pending")}
+                                }
+                            }
+                        }
+
+                        if let Some(ref tags) = meta.tags {
+                            div {
+                                class: "flex flex-wrap gap-2 mt-3 sm:mt-4",
+                                for tag in tags.iter() {
+                                    Link {
+                                        key: "{tag.id}",
+                                        to: Route::TagsTag { tag: tag.to_string() }.localized(route_locale.as_deref()),
+                                        class: "inline-flex items-center text-xs text-muted-foreground hover:text-foreground transition-colors",
+                                        "#{ tag.label }"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(ref series) = meta.series {
+                        SeriesNav {
+                            series_id: series.id.clone(),
+                            slug: meta.slug.clone(),
+                            lang: meta.lang.clone(),
+                        }
+                    }
+
+                    div {
+                        key: "{markdown_key}",
+                        class: "prose prose-sm sm:prose-base lg:prose-lg max-w-none prose-slate dark:prose-invert",
+                        MarkdownRenderer {
+                            content: content_with_anchors.clone(),
+                            components: markdown_components,
+                        }
+                    }
+
+                }
+                if let Some(footer) = footer {
+                    {footer}
+                }
+                if !toc_items.is_empty() {
+                    div {
+                        class: "hidden lg:block fixed top-4 bottom-4 left-0 right-[calc(50%+32.5ch)] z-20",
+                        div {
+                            class: "h-full pl-6 pr-4",
+                            RootContentToc { toc_items: toc_items.clone() }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,12 +6,15 @@ use crate::components::views::blog::row::{BlogRow, BlogRowTagMode};
 use crate::IO::blog;
 use dioxus::prelude::*;
 
+/// `lang` comes from a `/{lang}/blog/` URL and wins over the stored preference.
 #[component]
-pub fn BlogListView() -> Element {
+pub fn BlogListView(lang: Option<String>) -> Element {
     let preference = use_context::<PreferenceContext>();
     let locale = preference.locale();
     let posts_fut = use_server_future(move || {
-        let current_lang = resolve_locale(locale.read().as_deref()).to_string();
+        let current_lang = lang
+            .clone()
+            .unwrap_or_else(|| resolve_locale(locale.read().as_deref()).to_string());
         async move { blog::get_posts_by_lang(current_lang).await }
     })?;
 
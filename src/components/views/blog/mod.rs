@@ -1,3 +1,7 @@
+pub mod article;
 pub mod list;
 pub mod post;
+pub mod preview;
+pub mod related;
 pub mod row;
+pub mod translations;
@@ -0,0 +1,48 @@
+use crate::components::providers::interactive_provider::InteractiveContext;
+use crate::components::views::blog::article::BlogArticle;
+use crate::components::views::blog::post::BlogPostNotFound;
+use crate::IO::blog;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+#[component]
+pub fn BlogPreviewView(slug: String, lang: String, token: String) -> Element {
+    let mut interactive_context = use_context::<InteractiveContext>();
+    use_effect(move || {
+        interactive_context.post_focus.set(false);
+    });
+    let post_fut = use_server_future(move || {
+        let slug = slug.clone();
+        let lang = lang.clone();
+        let token = token.clone();
+        async move { blog::get_post_preview(slug, lang, token).await }
+    })?;
+
+    match post_fut() {
+        Some(Ok(Some(post))) => {
+            let status = match post.meta.publish_at.as_deref() {
+                Some(publish_at) if !post.meta.draft => {
+                    t!("blog_preview_scheduled", publish_at: publish_at)
+                }
+                _ => t!("blog_preview_draft"),
+            };
+            let notice = t!("blog_preview_notice", status: status);
+            rsx! {
+                BlogArticle {
+                    post,
+                    notice: rsx! {
+                        div {
+                            class: "mb-6 py-2 border-y border-zinc-200 dark:border-zinc-800 font-mono text-xs text-muted-foreground",
+                            "{notice}"
+                        }
+                    },
+                }
+            }
+        }
+        _ => {
+            rsx! {
+                BlogPostNotFound {}
+            }
+        }
+    }
+}
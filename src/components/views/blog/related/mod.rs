@@ -0,0 +1,85 @@
+use crate::components::providers::preference_provider::use_route_locale;
+use crate::components::views::blog::row::{BlogRow, BlogRowTagMode};
+use crate::root::Route;
+use crate::IO::blog;
+use dioxus::prelude::*;
+
+#[component]
+pub fn BlogPostFooter(slug: String, lang: String) -> Element {
+    let route_locale = use_route_locale();
+    let query_slug = slug.clone();
+    let query_lang = lang.clone();
+    let neighbors_fut = use_server_future(move || {
+        let slug = query_slug.clone();
+        let lang = query_lang.clone();
+        async move { blog::get_post_neighbors(slug, lang).await }
+    })?;
+    let related_fut = use_server_future(move || {
+        let slug = slug.clone();
+        let lang = lang.clone();
+        async move { blog::get_related_posts(slug, lang).await }
+    })?;
+
+    let neighbors = match neighbors_fut() {
+        Some(Ok(neighbors)) => neighbors,
+        _ => Default::default(),
+    };
+    let related = match related_fut() {
+        Some(Ok(related)) => related,
+        _ => Vec::new(),
+    };
+
+    if neighbors.previous.is_none() && neighbors.next.is_none() && related.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        footer {
+            class: "mt-10 pt-6 border-t border-zinc-200 dark:border-zinc-800 space-y-8",
+            if neighbors.previous.is_some() || neighbors.next.is_some() {
+                nav {
+                    class: "grid grid-cols-1 sm:grid-cols-2 gap-4 text-sm",
+                    if let Some(previous) = neighbors.previous {
+                        Link {
+                            to: Route::BlogPost { slug: previous.slug.clone() }.localized(route_locale.as_deref()),
+                            class: "group flex flex-col gap-1 min-w-0",
+                            span { class: "font-mono text-xs text-muted-foreground", "← Previous" }
+                            span {
+                                class: "text-foreground group-hover:underline underline-offset-4 truncate",
+                                "{previous.title}"
+                            }
+                        }
+                    } else {
+                        span {}
+                    }
+                    if let Some(next) = neighbors.next {
+                        Link {
+                            to: Route::BlogPost { slug: next.slug.clone() }.localized(route_locale.as_deref()),
+                            class: "group flex flex-col gap-1 min-w-0 sm:items-end sm:text-right",
+                            span { class: "font-mono text-xs text-muted-foreground", "Next →" }
+                            span {
+                                class: "text-foreground group-hover:underline underline-offset-4 truncate max-w-full",
+                                "{next.title}"
+                            }
+                        }
+                    }
+                }
+            }
+            if !related.is_empty() {
+                section {
+                    h2 {
+                        class: "text-sm font-semibold tracking-normal text-foreground mb-2",
+                        "Related"
+                    }
+                    for post_meta in related {
+                        BlogRow {
+                            key: "{post_meta.slug}",
+                            post_meta: post_meta.clone(),
+                            tag_mode: BlogRowTagMode::FirstTag,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
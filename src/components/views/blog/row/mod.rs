@@ -1,3 +1,4 @@
+use crate::components::providers::preference_provider::use_route_locale;
 use crate::impls::blog::PostMetadata;
 use crate::root::Route;
 use dioxus::prelude::*;
@@ -13,6 +14,7 @@ pub enum BlogRowTagMode {
 
 #[component]
 pub fn BlogRow(post_meta: PostMetadata, tag_mode: BlogRowTagMode) -> Element {
+    let route_locale = use_route_locale();
     let slug = post_meta.slug.clone();
     let title = post_meta.title.clone();
     let date = post_meta.date.clone();
@@ -30,7 +32,7 @@ pub fn BlogRow(post_meta: PostMetadata, tag_mode: BlogRowTagMode) -> Element {
                 div {
                     class: "flex flex-col min-w-0",
                     Link {
-                        to: Route::BlogPost { slug: slug.clone() },
+                        to: Route::BlogPost { slug: slug.clone() }.localized(route_locale.as_deref()),
                         class: "text-base sm:text-lg tracking-tight text-foreground hover:underline underline-offset-4 transition-colors truncate min-w-0",
                         title: "{title}",
                         "{title}"
@@ -39,7 +41,7 @@ pub fn BlogRow(post_meta: PostMetadata, tag_mode: BlogRowTagMode) -> Element {
                         BlogRowTagMode::FirstTag => rsx! {
                             if let Some(first_tag) = tags.first() {
                                 Link {
-                                    to: Route::TagsTag { tag: first_tag.to_string() },
+                                    to: Route::TagsTag { tag: first_tag.to_string() }.localized(route_locale.as_deref()),
                                     class: "mt-0.5 inline-flex w-fit text-[11px] leading-none text-muted-foreground/75 hover:text-foreground transition-colors",
                                     "#{ first_tag.label }"
                                 }
@@ -54,7 +56,7 @@ pub fn BlogRow(post_meta: PostMetadata, tag_mode: BlogRowTagMode) -> Element {
                                     .map(|other_tag| rsx! {
                                         Link {
                                             key: "{other_tag}",
-                                            to: Route::TagsTag { tag: other_tag.id.clone() },
+                                            to: Route::TagsTag { tag: other_tag.id.clone() }.localized(route_locale.as_deref()),
                                             class: "inline-flex text-[11px] leading-none text-muted-foreground/75 hover:text-foreground transition-colors",
                                             title: "View tag",
                                             "#{ other_tag.label }"
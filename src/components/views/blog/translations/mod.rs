@@ -0,0 +1,72 @@
+use crate::components::providers::preference_provider::{
+    PreferenceContext, PreferenceStoreStoreExt,
+};
+use crate::impls::blog::ServedPost;
+use crate::impls::i18n;
+use crate::root::Route;
+use crate::IO::user;
+use dioxus::prelude::*;
+use dioxus_i18n::t;
+
+fn locale_name(code: &str) -> String {
+    i18n::find_locale(code).map_or_else(|| code.to_string(), |locale| locale.name.to_string())
+}
+
+/// Says when a post is shown in a fallback language and links its other
+/// translations. The links pin the language in the URL, so they switch this
+/// post only and leave the reader's preference alone. Dismissing the notice
+/// is remembered for the session.
+#[component]
+pub fn PostTranslations(served: ServedPost) -> Element {
+    let preference = use_context::<PreferenceContext>();
+    let mut dismissed = preference.fallback_notice_dismissed();
+    let slug = served.post.meta.slug.clone();
+    let show_notice = served.is_fallback() && !dismissed.cloned();
+    let requested_name = locale_name(&served.requested_lang);
+    let served_name = locale_name(&served.served_lang);
+    let translations = served
+        .available_langs
+        .iter()
+        .filter(|lang| **lang != served.served_lang)
+        .map(|lang| (lang.clone(), locale_name(lang)))
+        .collect::<Vec<_>>();
+
+    rsx! {
+        if show_notice {
+            div {
+                role: "status",
+                class: "mb-6 py-2 border-y border-zinc-200 dark:border-zinc-800 font-mono text-xs text-muted-foreground flex items-center justify-between gap-3",
+                span { {t!("blog_translations_fallback_notice", requested: requested_name, served: served_name)} }
+                button {
+                    r#type: "button",
+                    aria_label: t!("blog_translations_dismiss"),
+                    class: "cursor-pointer opacity-50 hover:opacity-100 hover:text-foreground transition-opacity",
+                    onclick: move |_| {
+                        dismissed.set(true);
+                        spawn(async move {
+                            if let Err(e) = user::dismiss_fallback_notice().await {
+                                log::warn!("Failed to save notice dismissal to session: {e}");
+                            }
+                        });
+                    },
+                    "×"
+                }
+            }
+        }
+        if !translations.is_empty() {
+            nav {
+                aria_label: t!("blog_translations_label"),
+                class: "mb-6 flex flex-wrap items-center gap-2 font-mono text-xs text-muted-foreground",
+                span { class: "opacity-50", {t!("blog_translations_also_in")} }
+                for (lang, name) in translations {
+                    Link {
+                        key: "{lang}",
+                        to: Route::LocalizedBlogPost { lang: lang.clone(), slug: slug.clone() },
+                        class: "hover:text-foreground hover:underline underline-offset-4 transition-colors",
+                        "{name}"
+                    }
+                }
+            }
+        }
+    }
+}
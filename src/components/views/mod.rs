@@ -1,4 +1,6 @@
 pub mod about;
 pub mod blog;
 pub mod not_found;
+pub mod search;
+pub mod series;
 pub mod tags;
@@ -0,0 +1,104 @@
+use crate::components::common::layout_cell::{LayoutCell, LayoutCellPadding};
+use crate::components::providers::preference_provider::{
+    resolve_locale, PreferenceContext, PreferenceStoreStoreExt,
+};
+use crate::components::search::engine::search_offline;
+use crate::components::views::blog::row::{BlogRow, BlogRowTagMode};
+use crate::impls::search::SEARCH_QUERY_MAX_CHARS;
+use crate::root::Route;
+use crate::IO::blog;
+use dioxus::prelude::*;
+
+fn normalize_query(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[component]
+pub fn SearchView(q: String) -> Element {
+    let preference = use_context::<PreferenceContext>();
+    let locale = preference.locale();
+    let navigator = use_navigator();
+    let mut query = use_signal(|| q.clone());
+
+    let hits_res = use_resource(move || {
+        let current_lang = resolve_locale(locale.read().as_deref()).to_string();
+        let current_query = normalize_query(&query.read());
+        async move {
+            if current_query.is_empty() {
+                return Vec::new();
+            }
+            if let Some(hits) = search_offline(&current_lang, &current_query).await {
+                return hits;
+            }
+            blog::search_posts(current_lang, current_query)
+                .await
+                .unwrap_or_default()
+        }
+    });
+
+    let hits = hits_res.cloned().unwrap_or_default();
+    let query_text = query.read().clone();
+    let has_query = !normalize_query(&query_text).is_empty();
+
+    rsx! {
+        LayoutCell {
+            padding: LayoutCellPadding::Normal,
+            div {
+                class: "space-y-4",
+                div {
+                    h1 {
+                        class: "text-sm sm:text-base font-semibold tracking-normal text-foreground leading-relaxed",
+                        "Search • {hits.len()}"
+                    }
+                }
+                input {
+                    r#type: "search",
+                    class: "w-full bg-transparent border-b border-zinc-200 dark:border-zinc-800 py-2 text-base text-foreground placeholder:text-muted-foreground/60 focus:outline-none",
+                    placeholder: "Search articles",
+                    autofocus: true,
+                    maxlength: "{SEARCH_QUERY_MAX_CHARS}",
+                    value: "{query_text}",
+                    oninput: move |event| {
+                        let value = event.value();
+                        query.set(value.clone());
+                        navigator.replace(Route::Search { q: value });
+                    },
+                }
+                if has_query && hits.is_empty() {
+                    div {
+                        class: "text-center py-12 text-muted-foreground",
+                        "No matching articles"
+                    }
+                } else {
+                    {hits.into_iter().map(|hit| {
+                        let key = hit.meta.slug.clone();
+                        rsx! {
+                            div {
+                                key: "{key}",
+                                class: "space-y-1",
+                                BlogRow {
+                                    post_meta: hit.meta,
+                                    tag_mode: BlogRowTagMode::FirstTag,
+                                }
+                                p {
+                                    class: "text-sm text-muted-foreground leading-relaxed",
+                                    for (index, segment) in hit.snippet.into_iter().enumerate() {
+                                        if segment.highlighted {
+                                            mark {
+                                                key: "{index}",
+                                                class: "bg-transparent text-foreground font-medium",
+                                                "{segment.text}"
+                                            }
+                                        } else {
+                                            span { key: "{index}", "{segment.text}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    })}
+                }
+            }
+        }
+    }
+}
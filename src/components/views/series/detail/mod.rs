@@ -0,0 +1,82 @@
+use crate::components::common::layout_cell::{LayoutCell, LayoutCellPadding};
+use crate::components::providers::preference_provider::{
+    resolve_locale, PreferenceContext, PreferenceStoreStoreExt,
+};
+use crate::components::views::blog::row::{BlogRow, BlogRowTagMode};
+use crate::root::Route;
+use crate::IO::blog;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SeriesView(id: String, lang: Option<String>) -> Element {
+    let preference = use_context::<PreferenceContext>();
+    let locale = preference.locale();
+    let query_id = id.clone();
+    let route_lang = lang.clone();
+
+    let series_fut = use_server_future(move || {
+        let current_lang = route_lang
+            .clone()
+            .unwrap_or_else(|| resolve_locale(locale.read().as_deref()).to_string());
+        let id = query_id.clone();
+        async move { blog::get_series(id, current_lang).await }
+    })?;
+
+    let series = match series_fut() {
+        Some(Ok(series)) => series,
+        _ => None,
+    };
+
+    rsx! {
+        LayoutCell {
+            padding: LayoutCellPadding::Normal,
+            if let Some(series) = series {
+                div {
+                    class: "space-y-1",
+                    div {
+                        class: "mb-5",
+                        h1 {
+                            class: "text-sm sm:text-base font-semibold tracking-normal text-foreground mb-2 leading-relaxed",
+                            "Series of {series.title} • {series.posts.len()}"
+                        }
+                    }
+                    {series.posts.into_iter().enumerate().map(|(index, post_meta)| {
+                        let key = post_meta.slug.clone();
+                        let part = index + 1;
+                        rsx! {
+                            div {
+                                key: "{key}",
+                                class: "flex items-baseline gap-3",
+                                span {
+                                    class: "shrink-0 w-6 font-mono text-sm text-muted-foreground opacity-60",
+                                    "{part:02}"
+                                }
+                                div {
+                                    class: "min-w-0 flex-1",
+                                    BlogRow {
+                                        post_meta: post_meta,
+                                        tag_mode: BlogRowTagMode::FirstTag,
+                                    }
+                                }
+                            }
+                        }
+                    })}
+                }
+            } else {
+                div {
+                    class: "text-center py-8 sm:py-12 text-muted-foreground space-y-3 max-w-md mx-auto",
+                    div {
+                        class: "text-sm sm:text-base leading-relaxed",
+                        "No articles found for this series"
+                    }
+                    Link {
+                        to: Route::BlogList {}.localized(lang.as_deref()),
+                        class: "inline-flex items-center text-sm sm:text-base text-primary hover:text-primary/80 transition-colors min-h-[44px] justify-center",
+                        "← "
+                        "Blog"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::components::providers::preference_provider::use_route_locale;
+use crate::root::Route;
+use crate::IO::blog;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SeriesNav(series_id: String, slug: String, lang: String) -> Element {
+    let route_locale = use_route_locale();
+    let query_id = series_id.clone();
+    let series_fut = use_server_future(move || {
+        let id = query_id.clone();
+        let lang = lang.clone();
+        async move { blog::get_series(id, lang).await }
+    })?;
+
+    let Some(Ok(Some(series))) = series_fut() else {
+        return rsx! {};
+    };
+    let Some(current_index) = series.posts.iter().position(|post| post.slug == slug) else {
+        return rsx! {};
+    };
+    let total = series.posts.len();
+    let previous = current_index
+        .checked_sub(1)
+        .and_then(|index| series.posts.get(index))
+        .cloned();
+    let next = series.posts.get(current_index + 1).cloned();
+
+    rsx! {
+        aside {
+            class: "mb-7 py-3 border-y border-zinc-200 dark:border-zinc-800 text-sm space-y-2",
+            div {
+                class: "flex items-center justify-between gap-4",
+                Link {
+                    to: Route::SeriesDetail { id: series.id.clone() }.localized(route_locale.as_deref()),
+                    class: "font-medium text-foreground hover:underline underline-offset-4 truncate",
+                    "{series.title}"
+                }
+                span {
+                    class: "shrink-0 font-mono text-xs text-muted-foreground",
+                    "{current_index + 1}/{total}"
+                }
+            }
+            ol {
+                class: "space-y-1",
+                for (index, post) in series.posts.iter().enumerate() {
+                    li {
+                        key: "{post.slug}",
+                        class: "flex items-baseline gap-2",
+                        span { class: "font-mono text-xs text-muted-foreground opacity-60", "{index + 1:02}" }
+                        if index == current_index {
+                            span { aria_current: "page", class: "text-foreground", "{post.title}" }
+                        } else {
+                            Link {
+                                to: Route::BlogPost { slug: post.slug.clone() }.localized(route_locale.as_deref()),
+                                class: "text-muted-foreground hover:text-foreground transition-colors",
+                                "{post.title}"
+                            }
+                        }
+                    }
+                }
+            }
+            if previous.is_some() || next.is_some() {
+                div {
+                    class: "flex items-center justify-between gap-4 pt-1 text-xs",
+                    if let Some(previous) = previous {
+                        Link {
+                            to: Route::BlogPost { slug: previous.slug.clone() }.localized(route_locale.as_deref()),
+                            class: "text-muted-foreground hover:text-foreground transition-colors truncate",
+                            "← {previous.title}"
+                        }
+                    } else {
+                        span {}
+                    }
+                    if let Some(next) = next {
+                        Link {
+                            to: Route::BlogPost { slug: next.slug.clone() }.localized(route_locale.as_deref()),
+                            class: "text-muted-foreground hover:text-foreground transition-colors truncate text-right",
+                            "{next.title} →"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -7,11 +7,14 @@ use crate::IO::blog;
 use dioxus::prelude::*;
 
 #[component]
-pub fn TagsListView() -> Element {
+pub fn TagsListView(lang: Option<String>) -> Element {
     let preference = use_context::<PreferenceContext>();
     let locale = preference.locale();
+    let route_lang = lang.clone();
     let tag_groups_fut = use_server_future(move || {
-        let current_lang = resolve_locale(locale.read().as_deref()).to_string();
+        let current_lang = route_lang
+            .clone()
+            .unwrap_or_else(|| resolve_locale(locale.read().as_deref()).to_string());
         async move { blog::get_tag_groups(current_lang).await }
     })?;
 
@@ -47,7 +50,7 @@ pub fn TagsListView() -> Element {
 
                             Link {
                                 class: "flex items-center space-x-2 sm:space-x-3",
-                                to: Route::TagsTag { tag: tag_id.clone() },
+                                to: Route::TagsTag { tag: tag_id.clone() }.localized(lang.as_deref()),
                                 h2 {
                                     class: "text-sm sm:text-base font-medium text-foreground hover:underline underline-offset-4 leading-relaxed",
                                     "#{ tag_label }"
@@ -8,14 +8,17 @@ use crate::IO::blog;
 use dioxus::prelude::*;
 
 #[component]
-pub fn TagsTagView(tag: String) -> Element {
+pub fn TagsTagView(tag: String, lang: Option<String>) -> Element {
     let preference = use_context::<PreferenceContext>();
     let locale = preference.locale();
     let tag_id = tag.trim().to_lowercase();
     let query_tag_id = tag_id.clone();
+    let route_lang = lang.clone();
 
     let posts_fut = use_server_future(move || {
-        let current_lang = resolve_locale(locale.read().as_deref()).to_string();
+        let current_lang = route_lang
+            .clone()
+            .unwrap_or_else(|| resolve_locale(locale.read().as_deref()).to_string());
         let tag = query_tag_id.clone();
         async move { blog::get_posts_by_tag(tag, current_lang).await }
     })?;
@@ -57,7 +60,7 @@ pub fn TagsTagView(tag: String) -> Element {
                             "No articles found for this tag"
                         }
                         Link {
-                            to: Route::TagList {},
+                            to: Route::TagList {}.localized(lang.as_deref()),
                             class: "inline-flex items-center text-sm sm:text-base text-primary hover:text-primary/80 transition-colors min-h-[44px] justify-center",
                             "← "
                             "Tag"
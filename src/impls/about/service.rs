@@ -1,34 +1,20 @@
-use std::fs;
 use std::path::Path;
 
-const ABOUT_CN_PATH: &str = "content/about/about_cn.md";
-const ABOUT_EN_PATH: &str = "content/about/about_en.md";
+use crate::impls::blog::content_source;
+use crate::impls::i18n;
 
-pub fn get_about_markdown(lang: &str) -> String {
-    let normalized = normalize_locale(lang);
-    let primary = match normalized {
-        "en" => ABOUT_EN_PATH,
-        _ => ABOUT_CN_PATH,
-    };
-    let fallback = match normalized {
-        "en" => ABOUT_CN_PATH,
-        _ => ABOUT_EN_PATH,
-    };
-
-    read_markdown(primary).unwrap_or_else(|| read_markdown(fallback).unwrap_or_default())
-}
+const ABOUT_DIR: &str = "about";
 
-fn normalize_locale(value: &str) -> &str {
-    match value {
-        "en" => "en",
-        _ => "cn",
-    }
-}
-
-fn read_markdown(path: &str) -> Option<String> {
-    let file_path = Path::new(path);
-    if !file_path.exists() {
-        return None;
-    }
-    fs::read_to_string(file_path).ok()
+/// Reads `about_{lang}.md` from the content source, walking the locale's
+/// fallback chain when the page has not been written in that language.
+pub fn get_about_markdown(lang: &str) -> String {
+    let source = content_source();
+    i18n::fallback_chain(lang)
+        .into_iter()
+        .find_map(|code| {
+            source
+                .read_to_string(&Path::new(ABOUT_DIR).join(format!("about_{code}.md")))
+                .ok()
+        })
+        .unwrap_or_default()
 }
@@ -1,20 +1,87 @@
 use dioxus::fullstack::FileStream;
 use dioxus::prelude::ServerFnError;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-const POSTS_ROOT: &str = "content/posts";
+use crate::impls::blog::service::{can_preview_slug, is_slug_published};
+use crate::impls::blog::source::{content_source, ContentSource};
+use crate::impls::blog::store::POSTS_DIR;
+use crate::impls::blog::variants::{cache_dir, ensure_variant, is_fresh, write_atomically};
 
+/// Images of published posts. Unpublished posts' images are only served by
+/// [`get_preview_asset`].
 pub async fn get_post_asset(slug: &str, file: &str) -> Result<FileStream, ServerFnError> {
-    if !is_safe_segment(slug) || !is_safe_segment(file) || !is_supported_asset(file) {
+    if !is_slug_published(slug) {
         return Err(not_found());
     }
+    serve_post_asset(slug, file).await
+}
 
-    let path = PathBuf::from(POSTS_ROOT)
-        .join(slug)
-        .join(format!("img/{file}"));
+/// Images of any post, for holders of a valid preview token.
+pub async fn get_preview_asset(
+    slug: &str,
+    lang: &str,
+    token: &str,
+    file: &str,
+) -> Result<FileStream, ServerFnError> {
+    if !can_preview_slug(slug, lang, token) {
+        return Err(not_found());
+    }
+    serve_post_asset(slug, file).await
+}
+
+async fn serve_post_asset(slug: &str, file: &str) -> Result<FileStream, ServerFnError> {
+    let path = post_asset_path(slug, file).ok_or_else(not_found)?;
+    let source = content_source();
+    let path = if source.is_file(&path) {
+        let cached = cache_dir().join(slug).join(file);
+        tokio::task::spawn_blocking(move || asset_on_disk(source.as_ref(), &path, &cached))
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(not_found)?
+    } else {
+        ensure_variant(slug, file).await.ok_or_else(not_found)?
+    };
     FileStream::from_path(path).await.map_err(|_| not_found())
 }
 
+/// Location of `/blog/{slug}/img/{file}` in the content source, or `None`
+/// when the request could escape the post directory or names an unsupported
+/// file type.
+pub fn post_asset_path(slug: &str, file: &str) -> Option<PathBuf> {
+    if !is_safe_segment(slug) || !is_safe_segment(file) || !is_supported_asset(file) {
+        return None;
+    }
+
+    Some(Path::new(POSTS_DIR).join(slug).join("img").join(file))
+}
+
+/// A file on disk to stream `path` from: the file itself when the source
+/// lives on disk, otherwise a copy in the image cache.
+fn asset_on_disk(source: &dyn ContentSource, path: &Path, cached: &Path) -> Option<PathBuf> {
+    if let Some(root) = source.local_root() {
+        return Some(root.join(path));
+    }
+    if is_fresh(cached, source.modified(path)) {
+        return Some(cached.to_path_buf());
+    }
+
+    let bytes = source.read_bytes(path).ok()?;
+    if let Some(parent) = cached.parent() {
+        fs::create_dir_all(parent).ok()?;
+    }
+    match write_atomically(cached, |partial| {
+        fs::write(partial, &bytes).map_err(|error| error.to_string())
+    }) {
+        Ok(()) => Some(cached.to_path_buf()),
+        Err(error) => {
+            log::warn!("failed to cache post asset {}: {error}", path.display());
+            None
+        }
+    }
+}
+
 fn is_safe_segment(value: &str) -> bool {
     !value.is_empty()
         && !value.starts_with('.')
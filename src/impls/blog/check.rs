@@ -0,0 +1,111 @@
+use crate::impls::blog::error::{line_at, ContentError, ContentErrorKind};
+use crate::impls::blog::links::find_broken_images;
+use crate::impls::blog::store::{load_content, SourcedPost};
+
+/// Components whose markdown form wraps content between an opening and a
+/// closing tag.
+const BLOCK_COMPONENTS: [&str; 1] = ["Mermaid"];
+
+pub struct ContentReport {
+    pub post_count: usize,
+    pub errors: Vec<ContentError>,
+}
+
+/// Loads the whole content tree from the content source and validates it,
+/// without touching the live content index.
+pub fn check_content() -> ContentReport {
+    let (posts, mut errors) = load_content();
+    for sourced in posts.iter() {
+        check_images(sourced, &mut errors);
+        check_components(sourced, &mut errors);
+    }
+
+    errors.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    ContentReport {
+        post_count: posts.len(),
+        errors,
+    }
+}
+
+fn check_images(sourced: &SourcedPost, errors: &mut Vec<ContentError>) {
+    let content = &sourced.post.content;
+    for (offset, url) in find_broken_images(content, &sourced.post.meta.slug) {
+        errors.push(ContentError::new(
+            &sourced.path,
+            Some(body_line(sourced, line_at(content, offset))),
+            ContentErrorKind::BrokenImage { url },
+        ));
+    }
+}
+
+fn check_components(sourced: &SourcedPost, errors: &mut Vec<ContentError>) {
+    for name in BLOCK_COMPONENTS {
+        for (line, opened) in unbalanced_tags(&sourced.post.content, name) {
+            errors.push(ContentError::new(
+                &sourced.path,
+                Some(body_line(sourced, line)),
+                ContentErrorKind::UnbalancedComponent {
+                    name: name.to_string(),
+                    opened,
+                },
+            ));
+        }
+    }
+}
+
+/// Lines (1-based within `content`) of `<name>` tags never closed and of
+/// `</name>` tags never opened. Self-closing tags and fenced code are ignored.
+fn unbalanced_tags(content: &str, name: &str) -> Vec<(usize, bool)> {
+    let open_tag = format!("<{name}");
+    let close_tag = format!("</{name}>");
+    let mut open_lines = Vec::new();
+    let mut problems = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(position) = next_tag(rest, &open_tag, &close_tag) {
+            let tail = &rest[position..];
+            if tail.starts_with(&close_tag) {
+                if open_lines.pop().is_none() {
+                    problems.push((line_number, false));
+                }
+                rest = &tail[close_tag.len()..];
+                continue;
+            }
+
+            let after_name = &tail[open_tag.len()..];
+            let is_tag = after_name.starts_with(['>', ' ', '/', '\t']) || after_name.is_empty();
+            let tag_end = after_name.find('>').map(|end| end + 1);
+            let self_closing = tag_end.is_some_and(|end| after_name[..end].ends_with("/>"));
+            if is_tag && !self_closing {
+                open_lines.push(line_number);
+            }
+            rest = &after_name[tag_end.unwrap_or(0)..];
+        }
+    }
+
+    problems.extend(open_lines.into_iter().map(|line| (line, true)));
+    problems
+}
+
+fn next_tag(text: &str, open_tag: &str, close_tag: &str) -> Option<usize> {
+    match (text.find(open_tag), text.find(close_tag)) {
+        (Some(open), Some(close)) => Some(open.min(close)),
+        (open, close) => open.or(close),
+    }
+}
+
+fn body_line(sourced: &SourcedPost, content_line: usize) -> usize {
+    sourced.body_line + content_line - 1
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use std::time::SystemTime;
+
+use crate::impls::blog::source::content_source;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+type DimensionsCache = HashMap<PathBuf, (Option<SystemTime>, Option<ImageDimensions>)>;
+
+static DIMENSIONS_CACHE: OnceLock<RwLock<DimensionsCache>> = OnceLock::new();
+
+/// Intrinsic size of an image in the content source, read from the SVG root
+/// element or the raster header. Cached per path until the file changes.
+pub fn image_dimensions(path: &Path) -> Option<ImageDimensions> {
+    let source = content_source();
+    let modified = source.modified(path);
+    let cache = DIMENSIONS_CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+
+    if let Ok(state) = cache.read() {
+        if let Some((cached_modified, dimensions)) = state.get(path) {
+            if *cached_modified == modified {
+                return *dimensions;
+            }
+        }
+    }
+
+    let dimensions = source
+        .read_bytes(path)
+        .ok()
+        .and_then(|bytes| read_dimensions(path, &bytes));
+    if let Ok(mut state) = cache.write() {
+        state.insert(path.to_path_buf(), (modified, dimensions));
+    }
+    dimensions
+}
+
+fn read_dimensions(path: &Path, bytes: &[u8]) -> Option<ImageDimensions> {
+    let is_svg = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+
+    if is_svg {
+        return svg_dimensions(&String::from_utf8_lossy(bytes));
+    }
+
+    match imagesize::blob_size(bytes) {
+        Ok(size) => Some(ImageDimensions {
+            width: u32::try_from(size.width).ok()?,
+            height: u32::try_from(size.height).ok()?,
+        }),
+        Err(error) => {
+            log::warn!("failed to read image size {}: {error}", path.display());
+            None
+        }
+    }
+}
+
+fn svg_dimensions(text: &str) -> Option<ImageDimensions> {
+    let start = text.find("<svg")?;
+    let end = start + text[start..].find('>')?;
+    let tag = &text[start..end];
+
+    let width = svg_attribute(tag, "width").and_then(parse_svg_length);
+    let height = svg_attribute(tag, "height").and_then(parse_svg_length);
+    if let (Some(width), Some(height)) = (width, height) {
+        return Some(ImageDimensions { width, height });
+    }
+
+    let view_box = svg_attribute(tag, "viewBox")?
+        .split(|ch: char| ch.is_whitespace() || ch == ',')
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse::<f64>().ok())
+        .collect::<Vec<_>>();
+    let [_, _, width, height] = view_box.as_slice() else {
+        return None;
+    };
+    Some(ImageDimensions {
+        width: width.round() as u32,
+        height: height.round() as u32,
+    })
+}
+
+fn svg_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let preceded_by_space = rest[..index]
+            .chars()
+            .last()
+            .is_some_and(char::is_whitespace);
+        let after = rest[index + name.len()..].trim_start();
+        if preceded_by_space {
+            if let Some(value) = after.strip_prefix('=') {
+                let value = value.trim_start();
+                let quote = value.chars().next()?;
+                if quote == '"' || quote == '\'' {
+                    let value = &value[1..];
+                    return value.find(quote).map(|end| &value[..end]);
+                }
+            }
+        }
+        rest = &rest[index + name.len()..];
+    }
+    None
+}
+
+// Percentages and relative units have no intrinsic size; let viewBox decide.
+fn parse_svg_length(value: &str) -> Option<u32> {
+    let number = value.trim().strip_suffix("px").unwrap_or(value.trim());
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|value| *value > 0.0)
+        .map(|value| value.round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_svg_width_and_height() {
+        let svg = r#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" width="320px" height="200">"#;
+        assert_eq!(
+            svg_dimensions(svg),
+            Some(ImageDimensions {
+                width: 320,
+                height: 200
+            })
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_view_box() {
+        let svg = r#"<svg width="100%" viewBox="0 0 640.4 480"><rect/></svg>"#;
+        assert_eq!(
+            svg_dimensions(svg),
+            Some(ImageDimensions {
+                width: 640,
+                height: 480
+            })
+        );
+        assert_eq!(svg_dimensions("<svg><rect/></svg>"), None);
+    }
+
+    #[test]
+    fn ignores_attributes_that_only_end_with_the_name() {
+        let tag = r#"<svg data-width="1" width="2""#;
+        assert_eq!(svg_attribute(tag, "width"), Some("2"));
+    }
+}
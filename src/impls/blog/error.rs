@@ -0,0 +1,224 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// A problem found while loading `content/`, located at `path:line` when the
+/// line is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub kind: ContentErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentErrorKind {
+    Unreadable(String),
+    InvalidMeta(String),
+    InvalidFrontMatter(String),
+    InvalidDefinitions(String),
+    MissingDate {
+        lang: String,
+    },
+    InvalidDate {
+        value: String,
+    },
+    InvalidPublishAt {
+        value: String,
+    },
+    MissingTitle {
+        lang: String,
+    },
+    DuplicateSlug {
+        slug: String,
+        lang: String,
+        first: PathBuf,
+    },
+    UnknownTag {
+        id: String,
+    },
+    UnknownSeries {
+        id: String,
+    },
+    BrokenImage {
+        url: String,
+    },
+    UnbalancedComponent {
+        name: String,
+        opened: bool,
+    },
+    OutdatedTranslation {
+        source_lang: String,
+        recorded: String,
+        current: String,
+    },
+}
+
+impl ContentError {
+    pub fn new(path: impl Into<PathBuf>, line: Option<usize>, kind: ContentErrorKind) -> Self {
+        Self {
+            path: path.into(),
+            line,
+            kind,
+        }
+    }
+}
+
+impl ContentErrorKind {
+    /// Warnings are reported but do not make the content invalid.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::OutdatedTranslation { .. })
+    }
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: {}", self.path.display(), self.kind),
+            None => write!(f, "{}: {}", self.path.display(), self.kind),
+        }
+    }
+}
+
+impl fmt::Display for ContentErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(error) => write!(f, "unreadable file: {error}"),
+            Self::InvalidMeta(error) => write!(f, "invalid meta.yml: {error}"),
+            Self::InvalidFrontMatter(error) => write!(f, "invalid front matter: {error}"),
+            Self::InvalidDefinitions(error) => write!(f, "invalid definitions: {error}"),
+            Self::MissingDate { lang } => write!(f, "missing date for language '{lang}'"),
+            Self::InvalidDate { value } => {
+                write!(
+                    f,
+                    "date '{value}' is not YYYY-MM-DD and sorts as 1970-01-01"
+                )
+            }
+            Self::InvalidPublishAt { value } => write!(
+                f,
+                "publish_at '{value}' is not a date or date-time; the post is never published"
+            ),
+            Self::MissingTitle { lang } => {
+                write!(
+                    f,
+                    "missing title for language '{lang}'; the slug is shown instead"
+                )
+            }
+            Self::DuplicateSlug { slug, lang, first } => write!(
+                f,
+                "slug '{slug}' ({lang}) is already used by {}",
+                first.display()
+            ),
+            Self::UnknownTag { id } => write!(f, "unknown tag id '{id}'"),
+            Self::UnknownSeries { id } => write!(f, "unknown series id '{id}'"),
+            Self::BrokenImage { url } => write!(f, "image '{url}' does not exist"),
+            Self::UnbalancedComponent { name, opened: true } => {
+                write!(f, "<{name}> is never closed")
+            }
+            Self::UnbalancedComponent {
+                name,
+                opened: false,
+            } => {
+                write!(f, "</{name}> has no matching <{name}>")
+            }
+            Self::OutdatedTranslation {
+                source_lang,
+                recorded,
+                current,
+            } => write!(
+                f,
+                "translated from '{source_lang}' at {recorded}, which has since changed to {current}; \
+                 set translated_from.hash to {current} once the translation is updated"
+            ),
+        }
+    }
+}
+
+/// 1-based line where `key` is set to `value` in YAML or TOML `text`, either
+/// as `key: value`, as an item of a `key: [a, b]` list or as a `- value` item
+/// of a block list under `key:`. Quotes around values are ignored.
+pub fn line_of_field(text: &str, key: &str, value: &str) -> Option<usize> {
+    // Indentation of the `key:` line whose block list is being read.
+    let mut list_indent = None;
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if let Some(parent) = list_indent {
+            match trimmed.strip_prefix('-') {
+                Some(item) if indent >= parent => {
+                    if unquote(item) == value {
+                        return Some(index + 1);
+                    }
+                    continue;
+                }
+                _ if trimmed.is_empty() || trimmed.starts_with('#') => continue,
+                _ => list_indent = None,
+            }
+        }
+
+        let entry = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+        let Some(rest) = entry
+            .strip_prefix(key)
+            .and_then(|rest| rest.trim_start().strip_prefix([':', '=']))
+        else {
+            continue;
+        };
+        let rest = rest.trim();
+        if rest.is_empty() {
+            list_indent = Some(indent);
+        } else if let Some(items) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            if items.split(',').any(|item| unquote(item) == value) {
+                return Some(index + 1);
+            }
+        } else if unquote(rest) == value {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+        .unwrap_or(value)
+}
+
+/// 1-based line containing byte `offset` of `text`.
+pub fn line_at(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_fields_by_key_and_value() {
+        let text =
+            "slug: post\ndate: 2024\ntitles:\n  en: \"2024\"\nseries: intro\nseries_order: 2\n";
+        assert_eq!(line_of_field(text, "date", "2024"), Some(2));
+        assert_eq!(line_of_field(text, "series", "intro"), Some(5));
+        assert_eq!(line_of_field(text, "series", "2"), None);
+        assert_eq!(line_of_field(text, "slug", "pos"), None);
+        assert_eq!(line_of_field("date = \"2024\"\n", "date", "2024"), Some(1));
+    }
+
+    #[test]
+    fn finds_list_items_under_their_key() {
+        let text = "aliases:\n  - idea\ntags:\n  - draft\n  - 'idea'\ntitle: idea\n";
+        assert_eq!(line_of_field(text, "tags", "idea"), Some(5));
+        assert_eq!(line_of_field(text, "title", "idea"), Some(6));
+        assert_eq!(
+            line_of_field("tags: [a, \"idea\"]\n", "tags", "idea"),
+            Some(1)
+        );
+        assert_eq!(line_of_field("tags:\nidea: x\n", "tags", "x"), None);
+    }
+
+    #[test]
+    fn finds_nested_fields() {
+        let text = "translated_from:\n  lang: en\n  hash: abc\n";
+        assert_eq!(line_of_field(text, "hash", "abc"), Some(3));
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::impls::blog::error::{line_at, ContentError, ContentErrorKind};
+use crate::impls::blog::translation::TranslationSource;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RawPostFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_order: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub untranslated: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translated_from: Option<TranslationSource>,
+}
+
+impl RawPostFields {
+    /// Fields set on `other` win over fields set on `self`.
+    pub fn merged_with(self, other: RawPostFields) -> RawPostFields {
+        RawPostFields {
+            slug: other.slug.or(self.slug),
+            title: other.title.or(self.title),
+            date: other.date.or(self.date),
+            tags: other.tags.or(self.tags),
+            series: other.series.or(self.series),
+            series_order: other.series_order.or(self.series_order),
+            summary: other.summary.or(self.summary),
+            draft: other.draft.or(self.draft),
+            publish_at: other.publish_at.or(self.publish_at),
+            untranslated: other.untranslated.or(self.untranslated),
+            translated_from: other.translated_from.or(self.translated_from),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrontMatterFormat {
+    Yaml,
+    Toml,
+}
+
+impl FrontMatterFormat {
+    fn fence(self) -> &'static str {
+        match self {
+            Self::Yaml => "---",
+            Self::Toml => "+++",
+        }
+    }
+}
+
+/// Splits a leading `---` (YAML) or `+++` (TOML) block off a markdown file.
+/// Returns the parsed fields and the remaining body; a block that fails to
+/// parse is reported and left in the body untouched.
+pub fn split_front_matter<'a>(
+    content: &'a str,
+    path: &Path,
+    errors: &mut Vec<ContentError>,
+) -> (RawPostFields, &'a str) {
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some((format, raw, body)) = find_front_matter(text) else {
+        return (RawPostFields::default(), content);
+    };
+
+    let parsed = match format {
+        FrontMatterFormat::Yaml => serde_yaml::from_str::<RawPostFields>(raw).map_err(|error| {
+            let line = error.location().map(|location| location.line());
+            (error.to_string(), line)
+        }),
+        FrontMatterFormat::Toml => parse_toml_fields(raw),
+    };
+
+    match parsed {
+        Ok(fields) => (fields, body),
+        Err((error, line)) => {
+            // The block starts after the opening fence line.
+            let line = line.map(|line| line + 1);
+            errors.push(ContentError::new(
+                path,
+                line,
+                ContentErrorKind::InvalidFrontMatter(error),
+            ));
+            (RawPostFields::default(), content)
+        }
+    }
+}
+
+fn find_front_matter(text: &str) -> Option<(FrontMatterFormat, &str, &str)> {
+    let (first_line, rest) = text.split_once('\n')?;
+    let format = match first_line.trim_end() {
+        "---" => FrontMatterFormat::Yaml,
+        "+++" => FrontMatterFormat::Toml,
+        _ => return None,
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == format.fence() || (format == FrontMatterFormat::Yaml && trimmed == "...") {
+            let raw = &rest[..offset];
+            let body = rest[offset + line.len()..].trim_start_matches(['\r', '\n']);
+            return Some((format, raw, body));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+fn parse_toml_fields(raw: &str) -> Result<RawPostFields, (String, Option<usize>)> {
+    let table = toml::from_str::<toml::Table>(raw).map_err(|error| {
+        let line = error.span().map(|span| line_at(raw, span.start));
+        (error.message().to_string(), line)
+    })?;
+    let mut value = toml::Value::Table(table);
+    stringify_toml_datetimes(&mut value);
+    value
+        .try_into()
+        .map_err(|error: toml::de::Error| (error.message().to_string(), None))
+}
+
+fn stringify_toml_datetimes(value: &mut toml::Value) {
+    if let toml::Value::Datetime(datetime) = value {
+        let text = datetime.to_string();
+        *value = toml::Value::String(text);
+        return;
+    }
+
+    match value {
+        toml::Value::Array(items) => items.iter_mut().for_each(stringify_toml_datetimes),
+        toml::Value::Table(table) => table.values_mut().for_each(stringify_toml_datetimes),
+        _ => {}
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::impls::blog::types::Post;
+
+/// Immutable snapshot of every post on disk, drafts included. Posts are kept
+/// newest first and shared by `Arc`, so lookups never copy content.
+#[derive(Default)]
+pub struct ContentIndex {
+    version: u64,
+    last_modified: Option<SystemTime>,
+    posts: Vec<Arc<Post>>,
+    languages: Vec<String>,
+    by_slug_lang: HashMap<(String, String), usize>,
+    by_slug: HashMap<String, Vec<usize>>,
+    by_lang: HashMap<String, Vec<usize>>,
+    by_tag_lang: HashMap<(String, String), Vec<usize>>,
+    by_series_lang: HashMap<(String, String), Vec<usize>>,
+}
+
+impl ContentIndex {
+    /// `posts` must already be sorted newest first.
+    pub fn build(posts: Vec<Post>, version: u64, last_modified: Option<SystemTime>) -> Self {
+        let mut index = Self {
+            version,
+            last_modified,
+            ..Self::default()
+        };
+
+        for (position, post) in posts.into_iter().enumerate() {
+            let meta = &post.meta;
+            let slug = meta.slug.clone();
+            let lang = meta.lang.clone();
+
+            index
+                .by_slug_lang
+                .insert((slug.clone(), lang.clone()), position);
+            index.by_slug.entry(slug).or_default().push(position);
+            for tag in meta.tags.iter().flatten() {
+                index
+                    .by_tag_lang
+                    .entry((tag.id.clone(), lang.clone()))
+                    .or_default()
+                    .push(position);
+            }
+            if let Some(series) = meta.series.as_ref() {
+                index
+                    .by_series_lang
+                    .entry((series.id.clone(), lang.clone()))
+                    .or_default()
+                    .push(position);
+            }
+            index.by_lang.entry(lang).or_default().push(position);
+            index.posts.push(Arc::new(post));
+        }
+
+        index.languages = index.by_lang.keys().cloned().collect();
+        index.languages.sort();
+        index
+    }
+
+    /// Changes whenever the content on disk changes.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+    }
+
+    pub fn posts(&self) -> impl Iterator<Item = &Arc<Post>> {
+        self.posts.iter()
+    }
+
+    /// Languages with at least one post, published or not.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    pub fn post(&self, slug: &str, lang: &str) -> Option<&Arc<Post>> {
+        self.by_slug_lang
+            .get(&(slug.to_string(), lang.to_string()))
+            .map(|position| &self.posts[*position])
+    }
+
+    pub fn posts_for_slug(&self, slug: &str) -> impl Iterator<Item = &Arc<Post>> {
+        self.resolve(self.by_slug.get(slug))
+    }
+
+    pub fn posts_for_lang(&self, lang: &str) -> impl Iterator<Item = &Arc<Post>> {
+        self.resolve(self.by_lang.get(lang))
+    }
+
+    pub fn posts_for_tag(&self, tag_id: &str, lang: &str) -> impl Iterator<Item = &Arc<Post>> {
+        self.resolve(
+            self.by_tag_lang
+                .get(&(tag_id.to_string(), lang.to_string())),
+        )
+    }
+
+    pub fn posts_for_series(
+        &self,
+        series_id: &str,
+        lang: &str,
+    ) -> impl Iterator<Item = &Arc<Post>> {
+        self.resolve(
+            self.by_series_lang
+                .get(&(series_id.to_string(), lang.to_string())),
+        )
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        positions: Option<&'a Vec<usize>>,
+    ) -> impl Iterator<Item = &'a Arc<Post>> + 'a {
+        positions
+            .into_iter()
+            .flatten()
+            .map(|position| &self.posts[*position])
+    }
+}
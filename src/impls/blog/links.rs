@@ -0,0 +1,224 @@
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+use std::ops::Range;
+
+use crate::impls::blog::assets::post_asset_path;
+use crate::impls::blog::dimensions::image_dimensions;
+use crate::impls::blog::source::content_source;
+use crate::impls::blog::variants::{responsive_sources, VARIANT_SIZES};
+use crate::impls::site::component_tag;
+
+struct Edit {
+    range: Range<usize>,
+    replacement: String,
+}
+
+struct PendingImage {
+    range: Range<usize>,
+    src: String,
+    title: String,
+    alt: String,
+}
+
+/// Resolves relative image and link targets in a post against
+/// `/blog/{slug}/`, and turns local images into `<PostImage/>` components
+/// carrying their intrinsic width/height and, for rasters, resized WebP/AVIF
+/// `srcset`s. Missing asset files are logged.
+pub fn rewrite_post_links(content: &str, slug: &str) -> String {
+    let mut edits = Vec::new();
+    let mut image: Option<PendingImage> = None;
+
+    for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Image {
+                link_type: LinkType::Inline,
+                dest_url,
+                title,
+                ..
+            }) if image.is_none() => {
+                if let Some(src) = resolve_relative_url(&dest_url, slug) {
+                    image = Some(PendingImage {
+                        range,
+                        src,
+                        title: title.to_string(),
+                        alt: String::new(),
+                    });
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(pending) = image.as_mut() {
+                    pending.alt.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some(pending) = image.take() {
+                    edits.push(image_edit(pending, slug));
+                }
+            }
+            Event::Start(Tag::Link {
+                link_type: LinkType::Inline,
+                dest_url,
+                ..
+            }) if image.is_none() => {
+                if let Some(resolved) = resolve_relative_url(&dest_url, slug) {
+                    validate_asset(&resolved, slug);
+                    if let Some(edit) = link_destination_edit(content, range, &dest_url, resolved) {
+                        edits.push(edit);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    apply_edits(content, edits)
+}
+
+/// Images that point at a post asset file which does not exist, as
+/// `(byte offset, url)` pairs. External and non-asset URLs are not checked.
+pub fn find_broken_images(content: &str, slug: &str) -> Vec<(usize, String)> {
+    Parser::new_ext(content, markdown_options())
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let url =
+                    resolve_relative_url(&dest_url, slug).unwrap_or_else(|| dest_url.to_string());
+                let (asset_slug, file) = asset_location(&url)?;
+                let exists = post_asset_path(asset_slug, file)
+                    .is_some_and(|path| content_source().is_file(&path));
+                (!exists).then_some((range.start, url))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn image_edit(image: PendingImage, slug: &str) -> Edit {
+    let size = validate_asset(&image.src, slug).and_then(|path| image_dimensions(&path));
+    let dimensions = size.map(|size| (size.width.to_string(), size.height.to_string()));
+    let sources = size.and_then(|size| {
+        let (asset_slug, file) = asset_location(&image.src)?;
+        responsive_sources(asset_slug, file, size.width)
+    });
+
+    let mut attributes = vec![("src", image.src.as_str()), ("alt", image.alt.as_str())];
+    if !image.title.is_empty() {
+        attributes.push(("title", image.title.as_str()));
+    }
+    if let Some((width, height)) = dimensions.as_ref() {
+        attributes.push(("width", width.as_str()));
+        attributes.push(("height", height.as_str()));
+    }
+    if let Some(sources) = sources.as_ref() {
+        attributes.push(("srcset", sources.srcset.as_str()));
+        if let Some(webp_srcset) = sources.webp_srcset.as_ref() {
+            attributes.push(("webp_srcset", webp_srcset.as_str()));
+        }
+        attributes.push(("avif_srcset", sources.avif_srcset.as_str()));
+        attributes.push(("sizes", VARIANT_SIZES));
+    }
+
+    Edit {
+        range: image.range,
+        replacement: component_tag("PostImage", &attributes),
+    }
+}
+
+/// Returns the content source path for `/blog/{slug}/img/{file}` URLs,
+/// warning when it does not exist. Other URLs are not checked.
+fn validate_asset(url: &str, slug: &str) -> Option<std::path::PathBuf> {
+    let (asset_slug, file) = asset_location(url)?;
+    let Some(asset_path) = post_asset_path(asset_slug, file) else {
+        log::warn!("post {slug} references unsupported asset {url}");
+        return None;
+    };
+    if !content_source().is_file(&asset_path) {
+        log::warn!("post {slug} references missing asset {url}");
+        return None;
+    }
+    Some(asset_path)
+}
+
+fn asset_location(url: &str) -> Option<(&str, &str)> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.strip_prefix("/blog/")?.split_once("/img/")
+}
+
+// Only the destination inside `(...)` is replaced so the link text keeps its
+// original markdown.
+fn link_destination_edit(
+    content: &str,
+    range: Range<usize>,
+    dest_url: &str,
+    resolved: String,
+) -> Option<Edit> {
+    let source = &content[range.clone()];
+    let open = source.rfind("](")? + 2;
+    let offset = source[open..].find(dest_url)?;
+    let start = range.start + open + offset;
+    Some(Edit {
+        range: start..start + dest_url.len(),
+        replacement: resolved,
+    })
+}
+
+fn apply_edits(content: &str, mut edits: Vec<Edit>) -> String {
+    if edits.is_empty() {
+        return content.to_string();
+    }
+
+    edits.sort_by_key(|edit| edit.range.start);
+    let mut output = String::with_capacity(content.len());
+    let mut cursor = 0;
+    for edit in edits {
+        if edit.range.start < cursor {
+            continue;
+        }
+        output.push_str(&content[cursor..edit.range.start]);
+        output.push_str(&edit.replacement);
+        cursor = edit.range.end;
+    }
+    output.push_str(&content[cursor..]);
+    output
+}
+
+fn resolve_relative_url(url: &str, slug: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty()
+        || url.starts_with('/')
+        || url.starts_with('#')
+        || url.starts_with('?')
+        || url.contains("://")
+        || url.starts_with("mailto:")
+        || url.starts_with("data:")
+    {
+        return None;
+    }
+
+    let split_at = url.find(['?', '#']).unwrap_or(url.len());
+    let (path, suffix) = url.split_at(split_at);
+
+    let mut segments = vec!["blog", slug];
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            value => segments.push(value),
+        }
+    }
+
+    let mut resolved = format!("/{}", segments.join("/"));
+    if path.ends_with('/') {
+        resolved.push('/');
+    }
+    resolved.push_str(suffix);
+    Some(resolved)
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+}
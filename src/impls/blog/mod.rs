@@ -1,17 +1,57 @@
 #[cfg(feature = "server")]
 mod assets;
 #[cfg(feature = "server")]
+mod check;
+#[cfg(feature = "server")]
+mod dimensions;
+#[cfg(feature = "server")]
+mod error;
+#[cfg(feature = "server")]
+mod front_matter;
+#[cfg(feature = "server")]
+mod index;
+#[cfg(feature = "server")]
+mod links;
+#[cfg(feature = "server")]
+mod preview;
+#[cfg(feature = "server")]
+mod related;
+#[cfg(feature = "server")]
+mod scaffold;
+#[cfg(feature = "server")]
 mod service;
 #[cfg(feature = "server")]
+mod source;
+#[cfg(feature = "server")]
 mod store;
+#[cfg(feature = "server")]
+mod translation;
 mod types;
+#[cfg(feature = "server")]
+mod variants;
+#[cfg(feature = "server")]
+mod watcher;
 
 #[cfg(feature = "server")]
-pub use assets::get_post_asset;
+pub use assets::{get_post_asset, get_preview_asset, post_asset_path};
+#[cfg(feature = "server")]
+pub use check::{check_content, ContentReport};
+#[cfg(feature = "server")]
+pub use links::rewrite_post_links;
+#[cfg(feature = "server")]
+pub use related::{get_post_neighbors, get_related_posts, RELATED_POST_LIMIT};
+#[cfg(feature = "server")]
+pub use scaffold::{create_post, create_tag, create_translation, refresh_translation, NewPost};
 #[cfg(feature = "server")]
 pub use service::{
-    get_available_languages_for_slug, get_post_by_slug_and_lang, get_post_content_with_fallback,
-    get_post_with_fallback, get_posts_by_lang, get_posts_by_tag_and_lang, get_static_routes,
-    get_tag_groups,
+    content_last_modified, content_version, get_available_languages,
+    get_available_languages_for_slug, get_post_by_slug_and_lang, get_post_preview,
+    get_post_with_fallback, get_posts_by_lang, get_posts_by_tag_and_lang,
+    get_posts_with_content_by_lang, get_preview_links, get_series, get_static_routes,
+    get_tag_groups, scope_preview_assets,
 };
-pub use types::{Post, PostMetadata, TagGroup};
+#[cfg(feature = "server")]
+pub use source::{content_source, FilesystemSource};
+#[cfg(feature = "server")]
+pub use store::{load_content, set_content_source};
+pub use types::{Post, PostMetadata, PostNeighbors, Series, ServedPost, TagGroup};
@@ -0,0 +1,133 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const PREVIEW_SECRET_ENV: &str = "PREVIEW_SECRET";
+/// How long a preview link stays valid after it is signed.
+const PREVIEW_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Signs a token for previewing `slug` in `lang` until `expires_at`, written
+/// as `{expiry in unix seconds}.{hmac}`.
+pub fn sign_preview_token(slug: &str, lang: &str, expires_at: SystemTime) -> Option<String> {
+    let expires = unix_seconds(expires_at);
+    let mac = preview_mac(slug, lang, expires)?;
+    Some(format!(
+        "{expires}.{}",
+        encode_hex(&mac.finalize().into_bytes())
+    ))
+}
+
+pub fn verify_preview_token(slug: &str, lang: &str, token: &str, now: SystemTime) -> bool {
+    let Some((expires, signature)) = token.split_once('.') else {
+        return false;
+    };
+    let (Ok(expires), Some(expected)) = (expires.parse::<u64>(), decode_hex(signature)) else {
+        return false;
+    };
+    if expires <= unix_seconds(now) {
+        return false;
+    }
+    match preview_mac(slug, lang, expires) {
+        Some(mac) => mac.verify_slice(&expected).is_ok(),
+        None => false,
+    }
+}
+
+/// Preview page for `slug` in `lang`, valid for [`PREVIEW_TTL`] from now.
+pub fn preview_path(slug: &str, lang: &str) -> Option<String> {
+    let token = sign_preview_token(slug, lang, SystemTime::now() + PREVIEW_TTL)?;
+    Some(format!("/preview/{lang}/{slug}/{token}"))
+}
+
+/// Points the post's image URLs at the token-gated preview asset route, so
+/// images of unpublished posts load without being public.
+pub fn scope_assets_to_preview(content: &str, slug: &str, lang: &str, token: &str) -> String {
+    content.replace(
+        &format!("/blog/{slug}/img/"),
+        &format!("/api/blog/preview/{slug}/{lang}/{token}/img/"),
+    )
+}
+
+fn preview_mac(slug: &str, lang: &str, expires: u64) -> Option<HmacSha256> {
+    let secret = std::env::var(PREVIEW_SECRET_ENV)
+        .ok()
+        .filter(|value| !value.is_empty())?;
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(slug.as_bytes());
+    mac.update(b"\0");
+    mac.update(lang.as_bytes());
+    mac.update(b"\0");
+    mac.update(&expires.to_be_bytes());
+    Some(mac)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_secret<T>(test: impl FnOnce() -> T) -> T {
+        std::env::set_var(PREVIEW_SECRET_ENV, "test-secret");
+        test()
+    }
+
+    #[test]
+    fn accepts_tokens_until_they_expire() {
+        with_secret(|| {
+            let now = SystemTime::now();
+            let token = sign_preview_token("draft", "en", now + PREVIEW_TTL).unwrap();
+            assert!(verify_preview_token("draft", "en", &token, now));
+            assert!(!verify_preview_token(
+                "draft",
+                "en",
+                &token,
+                now + PREVIEW_TTL + Duration::from_secs(1)
+            ));
+        });
+    }
+
+    #[test]
+    fn rejects_tokens_for_other_posts_or_expiries() {
+        with_secret(|| {
+            let now = SystemTime::now();
+            let token = sign_preview_token("draft", "en", now + PREVIEW_TTL).unwrap();
+            assert!(!verify_preview_token("draft", "cn", &token, now));
+            assert!(!verify_preview_token("other", "en", &token, now));
+
+            let (_, signature) = token.split_once('.').unwrap();
+            let extended = format!("{}.{signature}", unix_seconds(now + PREVIEW_TTL) + 60);
+            assert!(!verify_preview_token("draft", "en", &extended, now));
+            assert!(!verify_preview_token("draft", "en", "not-a-token", now));
+        });
+    }
+
+    #[test]
+    fn scopes_only_the_posts_own_assets() {
+        let content = r#"<PostImage src="/blog/draft/img/a.png" srcset="/blog/draft/img/a-480w.webp 480w"/> /blog/other/img/b.png"#;
+        assert_eq!(
+            scope_assets_to_preview(content, "draft", "en", "1.ab"),
+            r#"<PostImage src="/api/blog/preview/draft/en/1.ab/img/a.png" srcset="/api/blog/preview/draft/en/1.ab/img/a-480w.webp 480w"/> /blog/other/img/b.png"#
+        );
+    }
+}
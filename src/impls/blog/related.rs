@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::impls::blog::service::{content_version, get_posts_with_content_by_lang};
+use crate::impls::blog::types::{Post, PostMetadata, PostNeighbors};
+use crate::impls::search::{markdown_to_plain_text, tokenize};
+use crate::utils::url::percent_decode;
+
+pub const RELATED_POST_LIMIT: usize = 3;
+
+const TAG_WEIGHT: f32 = 1.0;
+const CONTENT_WEIGHT: f32 = 1.0;
+
+type TermVector = HashMap<String, f32>;
+
+/// TF-IDF vectors of a language's published posts, in the order
+/// `get_posts_with_content_by_lang` returns them.
+struct CachedVectors {
+    signature: u64,
+    posts: Vec<Arc<Post>>,
+    vectors: Arc<Vec<TermVector>>,
+}
+
+static VECTOR_CACHE: OnceLock<RwLock<HashMap<String, CachedVectors>>> = OnceLock::new();
+
+/// Chronological neighbours within one language: `previous` is the next older
+/// post, `next` the next newer one.
+pub fn get_post_neighbors(slug: &str, lang: &str) -> PostNeighbors {
+    let decoded_slug = percent_decode(slug);
+    // Newest first, so the older neighbour sits after the current post.
+    let posts = get_posts_with_content_by_lang(lang);
+    let Some(index) = posts.iter().position(|post| post.meta.slug == decoded_slug) else {
+        return PostNeighbors::default();
+    };
+
+    PostNeighbors {
+        previous: posts.get(index + 1).map(|post| post.meta.clone()),
+        next: index
+            .checked_sub(1)
+            .and_then(|index| posts.get(index))
+            .map(|post| post.meta.clone()),
+    }
+}
+
+/// Ranks other posts in the same language by tag overlap (Jaccard) plus
+/// TF-IDF cosine similarity of their content.
+pub fn get_related_posts(slug: &str, lang: &str, limit: usize) -> Vec<PostMetadata> {
+    let decoded_slug = percent_decode(slug);
+    let (posts, vectors) = content_vectors(lang);
+    let Some(current_index) = posts.iter().position(|post| post.meta.slug == decoded_slug) else {
+        return Vec::new();
+    };
+
+    let current = &posts[current_index];
+    let current_tags = tag_ids(&current.meta);
+
+    let mut scored = posts
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != current_index)
+        .map(|(index, post)| {
+            let tag_score = jaccard(&current_tags, &tag_ids(&post.meta));
+            let content_score = cosine(&vectors[current_index], &vectors[index]);
+            (
+                TAG_WEIGHT * tag_score + CONTENT_WEIGHT * content_score,
+                post,
+            )
+        })
+        .filter(|(score, _)| *score > 0.0)
+        .collect::<Vec<_>>();
+
+    // `posts` is newest first and the sort is stable, so ties favour newer posts.
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, post)| post.meta.clone())
+        .collect()
+}
+
+/// Published posts in `lang` with their TF-IDF vectors, rebuilt only when the
+/// language's content version changes.
+fn content_vectors(lang: &str) -> (Vec<Arc<Post>>, Arc<Vec<TermVector>>) {
+    let signature = content_version(lang);
+    let cache = VECTOR_CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+
+    if let Ok(state) = cache.read() {
+        if let Some(cached) = state.get(lang) {
+            if cached.signature == signature {
+                return (cached.posts.clone(), cached.vectors.clone());
+            }
+        }
+    }
+
+    let posts = get_posts_with_content_by_lang(lang);
+    let vectors = Arc::new(tf_idf_vectors(&posts));
+
+    if let Ok(mut state) = cache.write() {
+        state.insert(
+            lang.to_string(),
+            CachedVectors {
+                signature,
+                posts: posts.clone(),
+                vectors: vectors.clone(),
+            },
+        );
+    }
+
+    (posts, vectors)
+}
+
+fn tf_idf_vectors(posts: &[Arc<Post>]) -> Vec<TermVector> {
+    let term_counts = posts
+        .iter()
+        .map(|post| {
+            let mut counts = HashMap::<String, u32>::new();
+            for token in tokenize(&markdown_to_plain_text(&post.content)) {
+                *counts.entry(token.term).or_insert(0) += 1;
+            }
+            counts
+        })
+        .collect::<Vec<_>>();
+
+    let mut document_frequency = HashMap::<&str, u32>::new();
+    for counts in term_counts.iter() {
+        for term in counts.keys() {
+            *document_frequency.entry(term.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    let doc_count = posts.len() as f32;
+    term_counts
+        .iter()
+        .map(|counts| {
+            let total = counts.values().sum::<u32>().max(1) as f32;
+            counts
+                .iter()
+                .map(|(term, count)| {
+                    let df = document_frequency.get(term.as_str()).copied().unwrap_or(1) as f32;
+                    let idf = (doc_count / df).ln();
+                    (term.clone(), *count as f32 / total * idf)
+                })
+                .filter(|(_, weight)| *weight > 0.0)
+                .collect()
+        })
+        .collect()
+}
+
+fn cosine(a: &TermVector, b: &TermVector) -> f32 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot = small
+        .iter()
+        .filter_map(|(term, weight)| large.get(term).map(|other| weight * other))
+        .sum::<f32>();
+    let norm = |vector: &TermVector| vector.values().map(|w| w * w).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+fn tag_ids(meta: &PostMetadata) -> HashSet<&str> {
+    meta.tags
+        .iter()
+        .flatten()
+        .map(|tag| tag.id.as_str())
+        .collect()
+}
+
+fn jaccard(a: &HashSet<&str>, b: &HashSet<&str>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(pairs: &[(&str, f32)]) -> TermVector {
+        pairs
+            .iter()
+            .map(|(term, weight)| (term.to_string(), *weight))
+            .collect()
+    }
+
+    #[test]
+    fn cosine_ignores_vector_length() {
+        let a = vector(&[("matrix", 1.0), ("vector", 1.0)]);
+        let b = vector(&[("matrix", 2.0), ("vector", 2.0)]);
+        assert!((cosine(&a, &b) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine(&a, &vector(&[("attention", 1.0)])), 0.0);
+        assert_eq!(cosine(&a, &TermVector::new()), 0.0);
+    }
+
+    #[test]
+    fn jaccard_of_tag_sets() {
+        let a = HashSet::from(["idea", "resource"]);
+        let b = HashSet::from(["idea"]);
+        assert_eq!(jaccard(&a, &b), 0.5);
+        assert_eq!(jaccard(&HashSet::new(), &HashSet::new()), 0.0);
+    }
+}
@@ -0,0 +1,420 @@
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::impls::blog::front_matter::{split_front_matter, RawPostFields};
+use crate::impls::blog::source::{local_content_dir, MemorySource};
+use crate::impls::blog::store::{
+    load_content_from, normalize_tag_id, RawPostMeta, RawTagConfig, POSTS_DIR, SERIES_FILE,
+    TAGS_FILE,
+};
+use crate::impls::blog::translation::{body_hash, TranslationSource};
+use crate::impls::i18n;
+
+pub struct NewPost {
+    pub slug: String,
+    pub lang: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Creates `content/posts/{slug}/meta.yml` and `{lang}.md`. Returns the files
+/// written.
+pub fn create_post(request: NewPost) -> Result<Vec<PathBuf>, String> {
+    validate_segment("slug", &request.slug)?;
+    validate_locale(&request.lang)?;
+    let known_tags = read_tag_config()?
+        .tags
+        .into_iter()
+        .map(|tag| normalize_tag_id(&tag.id))
+        .collect::<Vec<_>>();
+    let tags = request
+        .tags
+        .iter()
+        .map(|tag| normalize_tag_id(tag))
+        .collect::<Vec<_>>();
+    if let Some(unknown) = tags.iter().find(|tag| !known_tags.contains(tag)) {
+        return Err(format!(
+            "unknown tag id '{unknown}'; add it first with `new-tag {unknown}`"
+        ));
+    }
+
+    let relative_dir = Path::new(POSTS_DIR).join(&request.slug);
+    let post_dir = local_content_dir().join(&relative_dir);
+    if post_dir.exists() {
+        return Err(format!("{} already exists", post_dir.display()));
+    }
+
+    let title = request.title.unwrap_or_else(|| request.slug.clone());
+    let meta = RawPostMeta {
+        fields: RawPostFields {
+            slug: Some(request.slug.clone()),
+            date: Some(Local::now().date_naive().format("%Y-%m-%d").to_string()),
+            tags: (!tags.is_empty()).then_some(tags),
+            draft: Some(true),
+            ..RawPostFields::default()
+        },
+        titles: BTreeMap::from([(request.lang.clone(), title.clone())]),
+        languages: BTreeMap::new(),
+    };
+
+    let meta_text = to_yaml(&meta)?;
+    let markdown_name = format!("{}.md", request.lang);
+    let markdown_text = format!("# {title}\n\n");
+    validate_generated(&[
+        (relative_dir.join("meta.yml"), meta_text.clone()),
+        (relative_dir.join(&markdown_name), markdown_text.clone()),
+    ])?;
+
+    fs::create_dir_all(post_dir.join("img")).map_err(|error| error.to_string())?;
+    let meta_path = post_dir.join("meta.yml");
+    write_new(&meta_path, &meta_text)?;
+    let markdown_path = post_dir.join(markdown_name);
+    write_new(&markdown_path, &markdown_text)?;
+    Ok(vec![meta_path, markdown_path])
+}
+
+/// Copies an existing language version of `slug` to `{lang}.md`, keeping its
+/// front matter, marking the copy `untranslated: true` and recording the
+/// source revision in `translated_from`.
+pub fn create_translation(slug: &str, lang: &str, from: Option<&str>) -> Result<PathBuf, String> {
+    validate_segment("slug", slug)?;
+    validate_locale(lang)?;
+    let relative_dir = Path::new(POSTS_DIR).join(slug);
+    let post_dir = local_content_dir().join(&relative_dir);
+    let target = post_dir.join(format!("{lang}.md"));
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let source = match from {
+        Some(from) => post_dir.join(format!("{from}.md")),
+        None => find_source_markdown(&post_dir, lang)?,
+    };
+    let text = read_file(&source)?;
+    let source_lang = source
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut errors = Vec::new();
+    let (fields, body) = split_front_matter(&text, &source, &mut errors);
+    if let Some(error) = errors.first() {
+        return Err(error.to_string());
+    }
+    let fields = RawPostFields {
+        untranslated: Some(true),
+        translated_from: Some(TranslationSource::of(&source_lang, body)),
+        ..fields
+    };
+
+    let front_matter = to_yaml(&fields)?;
+    let markdown_text = format!("---\n{front_matter}---\n\n{}", body.trim_start());
+    let mut generated = vec![(
+        relative_dir.join(format!("{lang}.md")),
+        markdown_text.clone(),
+    )];
+    let meta_path = post_dir.join("meta.yml");
+    if meta_path.is_file() {
+        generated.push((relative_dir.join("meta.yml"), read_file(&meta_path)?));
+    }
+    validate_generated(&generated)?;
+    write_new(&target, &markdown_text)?;
+    Ok(target)
+}
+
+/// Records that `{lang}.md` is up to date with its source by rewriting the
+/// `translated_from.hash` it carries, in its front matter or under
+/// `languages.{lang}` in `meta.yml`, to the source's current hash. Only the
+/// hash is replaced, so the rest of the file keeps its formatting. Returns the
+/// file changed, or `None` when the hash was already current.
+pub fn refresh_translation(slug: &str, lang: &str) -> Result<Option<PathBuf>, String> {
+    validate_segment("slug", slug)?;
+    validate_segment("language", lang)?;
+    let post_dir = local_content_dir().join(POSTS_DIR).join(slug);
+    let markdown_path = post_dir.join(format!("{lang}.md"));
+    let markdown_text = read_file(&markdown_path)?;
+
+    let mut errors = Vec::new();
+    let (fields, _) = split_front_matter(&markdown_text, &markdown_path, &mut errors);
+    if let Some(error) = errors.first() {
+        return Err(error.to_string());
+    }
+
+    let meta_path = post_dir.join("meta.yml");
+    let (path, text, recorded, search_from) = match fields.translated_from {
+        Some(recorded) => (markdown_path, markdown_text, recorded, 0),
+        None => {
+            let meta_text = read_file(&meta_path)?;
+            let meta = serde_yaml::from_str::<RawPostMeta>(&meta_text)
+                .map_err(|error| format!("{}: {error}", meta_path.display()))?;
+            let recorded = meta
+                .languages
+                .get(lang)
+                .and_then(|fields| fields.translated_from.clone())
+                .ok_or_else(|| format!("{slug} ({lang}) does not record translated_from"))?;
+            let search_from = language_block_offset(&meta_text, lang).unwrap_or(0);
+            (meta_path, meta_text, recorded, search_from)
+        }
+    };
+    if recorded.lang == lang {
+        return Err(format!(
+            "{slug} ({lang}) is recorded as translated from itself"
+        ));
+    }
+
+    let source_path = post_dir.join(format!("{}.md", recorded.lang));
+    let source_text = read_file(&source_path)?;
+    let (_, source_body) = split_front_matter(&source_text, &source_path, &mut errors);
+    let current = body_hash(source_body);
+    if current == recorded.hash {
+        return Ok(None);
+    }
+
+    let offset = text[search_from..]
+        .find(&recorded.hash)
+        .map(|offset| search_from + offset)
+        .ok_or_else(|| format!("could not find {} in {}", recorded.hash, path.display()))?;
+    let mut updated = text.clone();
+    updated.replace_range(offset..offset + recorded.hash.len(), &current);
+    fs::write(&path, updated)
+        .map_err(|error| format!("failed to write {}: {error}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// Byte offset just past the `{lang}:` key nested under `languages:` in
+/// `meta.yml`.
+fn language_block_offset(meta_text: &str, lang: &str) -> Option<usize> {
+    let start = if meta_text.starts_with("languages:") {
+        0
+    } else {
+        meta_text.find("\nlanguages:")? + 1
+    };
+    let key = format!("{lang}:");
+    let mut offset = start;
+    for (index, line) in meta_text[start..].split_inclusive('\n').enumerate() {
+        offset += line.len();
+        if index == 0 {
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            return None;
+        }
+        if line.trim() == key {
+            return Some(offset);
+        }
+    }
+    None
+}
+
+/// Appends a tag to `content/tags.yml` as text, leaving the existing entries
+/// and their formatting untouched. Languages without an explicit label get the
+/// id as a placeholder.
+pub fn create_tag(id: &str, labels: BTreeMap<String, String>) -> Result<PathBuf, String> {
+    let id = normalize_tag_id(id);
+    validate_segment("tag id", &id)?;
+
+    let config = read_tag_config()?;
+    if config
+        .tags
+        .iter()
+        .any(|tag| normalize_tag_id(&tag.id) == id)
+    {
+        return Err(format!("tag '{id}' already exists in {TAGS_FILE}"));
+    }
+
+    let mut all_labels = config
+        .tags
+        .iter()
+        .flat_map(|tag| tag.labels.keys().cloned())
+        .map(|lang| (lang, id.clone()))
+        .collect::<BTreeMap<_, _>>();
+    all_labels.extend(labels);
+
+    let path = local_content_dir().join(TAGS_FILE);
+    let existing = if path.exists() {
+        read_file(&path)?
+    } else {
+        "tags:\n".to_string()
+    };
+    let mut text = existing;
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(&tag_entry(&id, &all_labels)?);
+
+    // Guards against layouts the appended entry does not fit, e.g. a flow
+    // sequence (`tags: [...]`) or a file without a `tags:` key.
+    let appended = serde_yaml::from_str::<RawTagConfig>(&text)
+        .ok()
+        .is_some_and(|updated| updated.tags.len() == config.tags.len() + 1);
+    if !appended {
+        return Err(format!(
+            "could not append to {}; add the tag by hand",
+            path.display()
+        ));
+    }
+
+    fs::write(&path, text)
+        .map_err(|error| format!("failed to write {}: {error}", path.display()))?;
+    Ok(path)
+}
+
+/// One `tags.yml` entry, indented like the entries the file ships with.
+fn tag_entry(id: &str, labels: &BTreeMap<String, String>) -> Result<String, String> {
+    let mut entry = format!("  - id: {}\n", yaml_scalar(id)?);
+    if !labels.is_empty() {
+        entry.push_str("    labels:\n");
+        for (lang, label) in labels {
+            entry.push_str(&format!(
+                "      {}: {}\n",
+                yaml_scalar(lang)?,
+                yaml_scalar(label)?
+            ));
+        }
+    }
+    Ok(entry)
+}
+
+fn yaml_scalar(value: &str) -> Result<String, String> {
+    to_yaml(&value).map(|text| text.trim_end().to_string())
+}
+
+fn read_tag_config() -> Result<RawTagConfig, String> {
+    let path = local_content_dir().join(TAGS_FILE);
+    if !path.exists() {
+        return Ok(RawTagConfig::default());
+    }
+    let text = read_file(&path)?;
+    serde_yaml::from_str(&text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Loads the generated files next to the current tag and series definitions
+/// and refuses them if the content store would report any problem.
+fn validate_generated(files: &[(PathBuf, String)]) -> Result<(), String> {
+    let content_dir = local_content_dir();
+    let mut source = MemorySource::new();
+    for shared in [TAGS_FILE, SERIES_FILE] {
+        let path = content_dir.join(shared);
+        if path.is_file() {
+            source.insert(shared, read_file(&path)?);
+        }
+    }
+    for (path, text) in files {
+        source.insert(path.clone(), text.clone());
+    }
+
+    let (_, errors) = load_content_from(&source);
+    match errors
+        .iter()
+        .find(|error| error.path.starts_with(POSTS_DIR))
+    {
+        Some(error) => Err(format!("generated content is invalid: {error}")),
+        None => Ok(()),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("failed to read {}: {error}", path.display()))
+}
+
+fn find_source_markdown(post_dir: &Path, lang: &str) -> Result<PathBuf, String> {
+    let entries = fs::read_dir(post_dir)
+        .map_err(|error| format!("failed to read {}: {error}", post_dir.display()))?;
+    let mut candidates = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("md"))
+        .filter(|path| path.file_stem().and_then(|value| value.to_str()) != Some(lang))
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("{} has no language to translate from", post_dir.display()))
+}
+
+fn validate_locale(lang: &str) -> Result<(), String> {
+    let enabled = i18n::enabled_locales();
+    if enabled.iter().any(|locale| locale.code == lang) {
+        return Ok(());
+    }
+    let codes = enabled
+        .iter()
+        .map(|locale| locale.code)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(format!(
+        "language '{lang}' is not enabled in site.toml; use one of: {codes}"
+    ))
+}
+
+fn validate_segment(label: &str, value: &str) -> Result<(), String> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid {label} '{value}': use lowercase letters, digits, '_' or '-'"
+        ))
+    }
+}
+
+fn to_yaml(value: &impl serde::Serialize) -> Result<String, String> {
+    serde_yaml::to_string(value).map_err(|error| error.to_string())
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), String> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| std::io::Write::write_all(&mut file, contents.as_bytes()))
+        .map_err(|error| format!("failed to write {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_language_block_in_meta() {
+        let meta = "date: 2024-01-01\nlanguages:\n  en:\n    translated_from:\n      lang: cn\n      hash: aa\n  de:\n    translated_from:\n      lang: cn\n      hash: aa\n";
+        let offset = language_block_offset(meta, "de").unwrap();
+        assert!(meta[offset..].starts_with("    translated_from:"));
+        assert_eq!(
+            offset + meta[offset..].find("aa").unwrap(),
+            meta.rfind("aa").unwrap()
+        );
+        assert_eq!(language_block_offset(meta, "fr"), None);
+        assert_eq!(language_block_offset("en:\n  x: 1\n", "en"), None);
+    }
+
+    #[test]
+    fn tag_entries_match_the_shipped_layout() {
+        let labels = BTreeMap::from([
+            ("cn".to_string(), "想法".to_string()),
+            ("en".to_string(), "Ideas: raw".to_string()),
+        ]);
+        let entry = tag_entry("idea", &labels).unwrap();
+        assert!(entry.starts_with("  - id: idea\n    labels:\n      cn: 想法\n      en: "));
+
+        let text = format!("# tags\ntags:\n  - id: resource\n{entry}");
+        let config = serde_yaml::from_str::<RawTagConfig>(&text).unwrap();
+        assert_eq!(config.tags.len(), 2);
+        assert_eq!(config.tags[1].labels["en"], "Ideas: raw");
+    }
+
+    #[test]
+    fn validates_path_segments() {
+        assert!(validate_segment("slug", "my_post-2").is_ok());
+        assert!(validate_segment("slug", "../etc").is_err());
+        assert!(validate_segment("slug", "Upper").is_err());
+        assert!(validate_segment("slug", "").is_err());
+    }
+}
@@ -1,105 +1,180 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::SystemTime;
 
-use crate::impls::blog::types::{Post, PostMetadata, Tag, TagGroup};
+use crate::impls::blog::preview::{preview_path, scope_assets_to_preview, verify_preview_token};
+use crate::impls::blog::store::{content_index, normalize_tag_id, parse_date_or_default};
+use crate::impls::blog::types::{Post, PostMetadata, Series, ServedPost, Tag, TagGroup};
+use crate::impls::i18n;
 use crate::utils::url::percent_decode;
 
-#[cfg(feature = "server")]
-use crate::impls::blog::store::all_posts;
+fn is_published(meta: &PostMetadata, now: DateTime<Utc>) -> bool {
+    if meta.draft {
+        return false;
+    }
 
-#[cfg(not(feature = "server"))]
-fn all_posts() -> Vec<Post> {
-    Vec::new()
+    match meta.publish_at.as_deref() {
+        Some(value) => parse_publish_at(value).is_some_and(|publish_at| publish_at <= now),
+        None => true,
+    }
 }
 
-pub fn get_posts_by_lang(lang: &str) -> Vec<PostMetadata> {
-    let mut posts = all_posts()
-        .into_iter()
-        .filter(|post| post.meta.lang == lang)
-        .map(|post| post.meta)
-        .collect::<Vec<_>>();
+fn published<'a>(
+    posts: impl Iterator<Item = &'a Arc<Post>>,
+) -> impl Iterator<Item = &'a Arc<Post>> {
+    let now = Utc::now();
+    posts.filter(move |post| is_published(&post.meta, now))
+}
 
-    sort_posts_desc(&mut posts);
-    posts
+/// Most recent modification time of any file the content store reads.
+pub fn content_last_modified() -> Option<SystemTime> {
+    content_index().last_modified()
 }
 
-pub fn get_posts_by_tag_and_lang(tag: &str, lang: &str) -> Vec<PostMetadata> {
-    let parsed_tag = normalize_tag_id(&percent_decode(tag));
-    if parsed_tag.is_empty() {
-        return Vec::new();
+/// Changes whenever the content on disk or the set of published posts changes.
+pub fn content_version(lang: &str) -> u64 {
+    let index = content_index();
+    let mut hasher = DefaultHasher::new();
+    index.version().hash(&mut hasher);
+    for post in published(index.posts_for_lang(lang)) {
+        post.meta.slug.hash(&mut hasher);
     }
+    hasher.finish()
+}
+
+pub fn get_unpublished_posts() -> Vec<PostMetadata> {
+    let now = Utc::now();
+    content_index()
+        .posts()
+        .filter(|post| !is_published(&post.meta, now))
+        .map(|post| post.meta.clone())
+        .collect()
+}
 
-    let mut posts = all_posts()
+/// Signed preview paths for every unpublished post. Empty when no preview
+/// secret is configured.
+pub fn get_preview_links() -> Vec<(PostMetadata, String)> {
+    get_unpublished_posts()
         .into_iter()
-        .filter(|post| {
-            post.meta.lang == lang
-                && post
-                    .meta
-                    .tags
-                    .as_ref()
-                    .map(|tags| {
-                        tags.iter()
-                            .any(|item| item.id.as_str() == parsed_tag.as_str())
-                    })
-                    .unwrap_or(false)
+        .filter_map(|meta| {
+            let path = preview_path(&meta.slug, &meta.lang)?;
+            Some((meta, path))
         })
-        .map(|post| post.meta)
-        .collect::<Vec<_>>();
-
-    sort_posts_desc(&mut posts);
-    posts
+        .collect()
 }
 
-pub fn get_post_by_slug_and_lang(slug: &str, lang: &str) -> Option<Post> {
+pub fn get_post_preview(slug: &str, lang: &str, token: &str) -> Option<Arc<Post>> {
     let decoded_slug = percent_decode(slug);
-    all_posts()
-        .into_iter()
-        .find(|post| post.meta.slug == decoded_slug && post.meta.lang == lang)
+    if !verify_preview_token(&decoded_slug, lang, token, SystemTime::now()) {
+        return None;
+    }
+
+    content_index().post(&decoded_slug, lang).cloned()
 }
 
-pub fn get_post_with_fallback(slug: &str, lang: &str) -> Option<Post> {
-    if let Some(post) = get_post_by_slug_and_lang(slug, lang) {
-        return Some(post);
-    }
+/// Rewrites a rendered preview so its images are served through the preview
+/// token instead of the public asset route.
+pub fn scope_preview_assets(content: &str, slug: &str, lang: &str, token: &str) -> String {
+    scope_assets_to_preview(content, &percent_decode(slug), lang, token)
+}
 
-    let available_languages = get_available_languages_for_slug(slug);
-    if available_languages.is_empty() {
-        return None;
+/// Whether `slug` has a published version in any language. Assets of posts
+/// that do not are only served through a preview token.
+pub fn is_slug_published(slug: &str) -> bool {
+    let now = Utc::now();
+    content_index()
+        .posts_for_slug(&percent_decode(slug))
+        .any(|post| is_published(&post.meta, now))
+}
+
+pub fn can_preview_slug(slug: &str, lang: &str, token: &str) -> bool {
+    verify_preview_token(&percent_decode(slug), lang, token, SystemTime::now())
+}
+
+pub fn get_posts_by_lang(lang: &str) -> Vec<PostMetadata> {
+    published(content_index().posts_for_lang(lang))
+        .map(|post| post.meta.clone())
+        .collect()
+}
+
+/// Published posts in `lang`, newest first, sharing content with the index.
+pub fn get_posts_with_content_by_lang(lang: &str) -> Vec<Arc<Post>> {
+    published(content_index().posts_for_lang(lang))
+        .cloned()
+        .collect()
+}
+
+pub fn get_posts_by_tag_and_lang(tag: &str, lang: &str) -> Vec<PostMetadata> {
+    let parsed_tag = normalize_tag_id(&percent_decode(tag));
+    if parsed_tag.is_empty() {
+        return Vec::new();
     }
 
-    let fallback_lang = if available_languages.iter().any(|value| value == "en") {
-        "en".to_string()
-    } else {
-        available_languages[0].clone()
-    };
+    published(content_index().posts_for_tag(&parsed_tag, lang))
+        .map(|post| post.meta.clone())
+        .collect()
+}
 
-    get_post_by_slug_and_lang(slug, &fallback_lang)
+pub fn get_post_by_slug_and_lang(slug: &str, lang: &str) -> Option<Arc<Post>> {
+    let decoded_slug = percent_decode(slug);
+    let index = content_index();
+    index
+        .post(&decoded_slug, lang)
+        .filter(|post| is_published(&post.meta, Utc::now()))
+        .cloned()
 }
 
-pub fn get_post_content_with_fallback(slug: &str, lang: &str) -> Option<String> {
-    get_post_with_fallback(slug, lang).map(|post| post.content)
+/// The post in `lang`, or in the first language of its fallback chain that
+/// the post has been published in.
+pub fn get_post_with_fallback(slug: &str, lang: &str) -> Option<ServedPost<Arc<Post>>> {
+    let available_langs = get_available_languages_for_slug(slug);
+    let served_lang = if available_langs.iter().any(|value| value == lang) {
+        lang.to_string()
+    } else {
+        i18n::fallback_chain(lang)
+            .into_iter()
+            .find(|code| available_langs.iter().any(|value| value == code))
+            .map(str::to_string)
+            .or_else(|| available_langs.first().cloned())?
+    };
+
+    let post = get_post_by_slug_and_lang(slug, &served_lang)?;
+    Some(ServedPost {
+        post,
+        requested_lang: lang.to_string(),
+        served_lang,
+        available_langs,
+    })
 }
 
 pub fn get_available_languages_for_slug(slug: &str) -> Vec<String> {
     let decoded_slug = percent_decode(slug);
-    let mut langs = all_posts()
-        .into_iter()
-        .filter(|post| post.meta.slug == decoded_slug)
-        .map(|post| post.meta.lang)
+    let mut langs = published(content_index().posts_for_slug(&decoded_slug))
+        .map(|post| post.meta.lang.clone())
         .collect::<Vec<_>>();
     langs.sort();
     langs.dedup();
     langs
 }
 
+pub fn get_available_languages() -> Vec<String> {
+    let index = content_index();
+    index
+        .languages()
+        .iter()
+        .filter(|lang| published(index.posts_for_lang(lang)).next().is_some())
+        .cloned()
+        .collect()
+}
+
 pub fn get_tag_groups(lang: &str) -> Vec<TagGroup> {
     let mut grouped = HashMap::<String, Vec<PostMetadata>>::new();
     let mut tags_by_id = HashMap::<String, Tag>::new();
 
-    for post in all_posts()
-        .into_iter()
-        .filter(|post| post.meta.lang == lang)
-    {
+    for post in published(content_index().posts_for_lang(lang)) {
         if let Some(tags) = post.meta.tags.as_ref() {
             for tag in tags {
                 grouped
@@ -127,30 +202,58 @@ pub fn get_tag_groups(lang: &str) -> Vec<TagGroup> {
     groups
 }
 
-pub fn get_static_routes() -> Vec<String> {
-    let posts = all_posts();
-    let mut routes = BTreeSet::new();
-    routes.insert("/blog/".to_string());
-    routes.insert("/tags/".to_string());
-
-    let mut slugs = BTreeSet::new();
-    let mut tags = BTreeSet::new();
-
-    for post in posts {
-        slugs.insert(post.meta.slug);
-        if let Some(post_tags) = post.meta.tags {
-            for tag in post_tags {
-                tags.insert(tag.to_string());
-            }
-        }
+pub fn get_series(id: &str, lang: &str) -> Option<Series> {
+    let series_id = normalize_tag_id(&percent_decode(id));
+    if series_id.is_empty() {
+        return None;
     }
 
-    for slug in slugs {
-        routes.insert(format!("/blog/{slug}/"));
+    let mut posts = published(content_index().posts_for_series(&series_id, lang))
+        .map(|post| post.meta.clone())
+        .collect::<Vec<_>>();
+    if posts.is_empty() {
+        return None;
     }
 
-    for tag in tags {
-        routes.insert(format!("/tags/{tag}"));
+    posts.sort_by(|a, b| {
+        let a_order = a.series.as_ref().and_then(|series| series.order);
+        let b_order = b.series.as_ref().and_then(|series| series.order);
+        a_order
+            .unwrap_or(u32::MAX)
+            .cmp(&b_order.unwrap_or(u32::MAX))
+            .then_with(|| parse_date_or_default(&a.date).cmp(&parse_date_or_default(&b.date)))
+    });
+
+    let title = posts[0]
+        .series
+        .as_ref()
+        .map(|series| series.title.clone())
+        .unwrap_or_else(|| series_id.clone());
+
+    Some(Series {
+        id: series_id,
+        title,
+        posts,
+    })
+}
+
+/// Page routes under every language prefix, e.g. `/en/blog/{slug}/`. A post,
+/// tag or series page is only listed for languages it has published posts in.
+pub fn get_static_routes() -> Vec<String> {
+    let index = content_index();
+    let mut routes = BTreeSet::new();
+
+    for post in published(index.posts()) {
+        let lang = &post.meta.lang;
+        routes.insert(format!("/{lang}/blog/"));
+        routes.insert(format!("/{lang}/tags/"));
+        routes.insert(format!("/{lang}/blog/{}/", post.meta.slug));
+        if let Some(series) = post.meta.series.as_ref() {
+            routes.insert(format!("/{lang}/series/{}", series.id));
+        }
+        for tag in post.meta.tags.iter().flatten() {
+            routes.insert(format!("/{lang}/tags/{tag}"));
+        }
     }
 
     routes.into_iter().collect()
@@ -160,18 +263,21 @@ fn sort_posts_desc(posts: &mut [PostMetadata]) {
     posts.sort_by(|a, b| parse_date_or_default(&b.date).cmp(&parse_date_or_default(&a.date)));
 }
 
-fn normalize_tag_id(value: &str) -> String {
-    value.trim().to_lowercase()
-}
-
-fn parse_date_or_default(date: &str) -> NaiveDate {
-    if let Ok(value) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
-        return value;
+/// Parses `publish_at` as RFC 3339, a naive UTC date-time or a date.
+pub fn parse_publish_at(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Utc));
     }
 
-    if let Some(value) = NaiveDate::from_ymd_opt(1970, 1, 1) {
-        return value;
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(datetime.and_utc());
+        }
     }
 
-    NaiveDate::MIN
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
 }
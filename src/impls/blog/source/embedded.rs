@@ -0,0 +1,114 @@
+use include_dir::{include_dir, Dir};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::{ContentSignature, ContentSource};
+
+static CONTENT: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/content");
+
+/// The `content/` tree as it was at compile time, post images included, for
+/// single-binary deployments. It never changes, so nothing is watched.
+pub struct EmbeddedSource {
+    signature: ContentSignature,
+}
+
+impl EmbeddedSource {
+    pub fn new() -> Self {
+        let mut hasher = DefaultHasher::new();
+        hash_dir(&CONTENT, &mut hasher);
+        Self {
+            signature: ContentSignature {
+                hash: hasher.finish(),
+                last_modified: None,
+            },
+        }
+    }
+
+    fn dir(&self, path: &Path) -> Option<&'static Dir<'static>> {
+        if path.as_os_str().is_empty() {
+            Some(&CONTENT)
+        } else {
+            CONTENT.get_dir(path)
+        }
+    }
+}
+
+impl Default for EmbeddedSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentSource for EmbeddedSource {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let file = CONTENT
+            .get_file(path)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        file.contents_utf8()
+            .map(str::to_string)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not valid UTF-8"))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        CONTENT
+            .get_file(path)
+            .map(|file| file.contents().to_vec())
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        CONTENT.get_file(path).is_some()
+    }
+
+    fn list_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        let mut dirs = self
+            .dir(dir)
+            .map(|dir| dir.dirs().map(|child| child.path().to_path_buf()).collect())
+            .unwrap_or_else(Vec::new);
+        dirs.sort();
+        dirs
+    }
+
+    fn list_files(&self, dir: &Path) -> Vec<PathBuf> {
+        let mut files = self
+            .dir(dir)
+            .map(|dir| dir.files().map(|file| file.path().to_path_buf()).collect())
+            .unwrap_or_else(Vec::new);
+        files.sort();
+        files
+    }
+
+    fn signature(&self) -> ContentSignature {
+        self.signature
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        CONTENT
+            .get_file(path)?
+            .metadata()
+            .map(|metadata| metadata.modified())
+    }
+
+    fn file_len(&self, path: &Path) -> Option<u64> {
+        CONTENT
+            .get_file(path)
+            .map(|file| file.contents().len() as u64)
+    }
+
+    fn display_path(&self, path: &Path) -> PathBuf {
+        Path::new("embedded:content").join(path)
+    }
+}
+
+fn hash_dir(dir: &Dir<'_>, hasher: &mut DefaultHasher) {
+    for file in dir.files() {
+        file.path().hash(hasher);
+        file.contents().hash(hasher);
+    }
+    for child in dir.dirs() {
+        hash_dir(child, hasher);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{ContentSignature, ContentSource};
+
+/// Reads content from a directory on disk and reloads when it changes.
+pub struct FilesystemSource {
+    root: PathBuf,
+}
+
+impl FilesystemSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn list(&self, dir: &Path, keep: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(self.root.join(dir)) else {
+            return Vec::new();
+        };
+
+        let mut paths = entries
+            .flatten()
+            .filter(|entry| keep(&entry.path()))
+            .map(|entry| dir.join(entry.file_name()))
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+}
+
+impl ContentSource for FilesystemSource {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(self.root.join(path))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(self.root.join(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.root.join(path).is_file()
+    }
+
+    fn list_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        self.list(dir, |path| path.is_dir())
+    }
+
+    fn list_files(&self, dir: &Path) -> Vec<PathBuf> {
+        self.list(dir, |path| path.is_file())
+    }
+
+    fn signature(&self) -> ContentSignature {
+        let mut files = Vec::new();
+        collect_files_recursive(&self.root, &mut files);
+        files.sort();
+
+        let mut hasher = DefaultHasher::new();
+        let mut last_modified = None::<SystemTime>;
+        for file in files {
+            file.to_string_lossy().hash(&mut hasher);
+
+            if let Ok(metadata) = fs::metadata(&file) {
+                metadata.len().hash(&mut hasher);
+                if let Ok(modified) = metadata.modified() {
+                    last_modified =
+                        Some(last_modified.map_or(modified, |value| value.max(modified)));
+                    if let Ok(duration) = modified.duration_since(UNIX_EPOCH) {
+                        duration.as_secs().hash(&mut hasher);
+                        duration.subsec_nanos().hash(&mut hasher);
+                    }
+                }
+            }
+        }
+
+        ContentSignature {
+            hash: hasher.finish(),
+            last_modified,
+        }
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        fs::metadata(self.root.join(path))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn file_len(&self, path: &Path) -> Option<u64> {
+        fs::metadata(self.root.join(path))
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+    }
+
+    fn display_path(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    fn local_root(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+fn collect_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files_recursive(&path, files);
+            continue;
+        }
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{ContentSignature, ContentSource};
+
+/// Content held in memory, keyed by path relative to the content root. Used to
+/// validate generated files before they are written, and handy in tests.
+#[derive(Default)]
+pub struct MemorySource {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: impl Into<PathBuf>, text: impl Into<String>) {
+        self.files.insert(path.into(), text.into());
+    }
+}
+
+impl ContentSource for MemorySource {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn list_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .keys()
+            .filter_map(|path| path.strip_prefix(dir).ok())
+            .filter_map(|relative| {
+                let mut components = relative.components();
+                let first = components.next()?;
+                components.next().map(|_| dir.join(first))
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn list_files(&self, dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .keys()
+            .filter(|path| path.parent() == Some(dir))
+            .cloned()
+            .collect()
+    }
+
+    fn signature(&self) -> ContentSignature {
+        let mut hasher = DefaultHasher::new();
+        self.files.hash(&mut hasher);
+        ContentSignature {
+            hash: hasher.finish(),
+            last_modified: None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "embedded-content")]
+mod embedded;
+mod filesystem;
+mod memory;
+
+#[cfg(feature = "embedded-content")]
+pub use embedded::EmbeddedSource;
+pub use filesystem::FilesystemSource;
+pub use memory::MemorySource;
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::SystemTime;
+
+use crate::impls::site::site_config;
+
+const DEFAULT_CONTENT_DIR: &str = "content";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ContentSignature {
+    pub hash: u64,
+    pub last_modified: Option<SystemTime>,
+}
+
+/// Where the content store reads `posts/`, `tags.yml` and `series.yml` from,
+/// and where post images, the about pages and `robots.txt` are served from.
+/// Every path is relative to the content root, e.g. `posts/{slug}/meta.yml`.
+pub trait ContentSource: Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    /// Direct subdirectories of `dir`, sorted.
+    fn list_dirs(&self, dir: &Path) -> Vec<PathBuf>;
+
+    /// Files directly inside `dir`, sorted.
+    fn list_files(&self, dir: &Path) -> Vec<PathBuf>;
+
+    /// Changes whenever anything the store could read changes.
+    fn signature(&self) -> ContentSignature;
+
+    /// When `path` last changed, if the source tracks it.
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+
+    /// Size of `path` in bytes, if it is a file.
+    fn file_len(&self, path: &Path) -> Option<u64> {
+        self.read_bytes(path).ok().map(|bytes| bytes.len() as u64)
+    }
+
+    /// How `path` is shown in content errors.
+    fn display_path(&self, path: &Path) -> PathBuf {
+        path.to_path_buf()
+    }
+
+    /// Directory on disk backing this source. It is watched for live reloads
+    /// and post images are streamed straight from it.
+    fn local_root(&self) -> Option<&Path> {
+        None
+    }
+}
+
+static CONTENT_SOURCE: OnceLock<RwLock<Arc<dyn ContentSource>>> = OnceLock::new();
+
+/// The configured `content_dir`, or `content` in the working directory.
+fn content_dir() -> PathBuf {
+    site_config()
+        .content_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONTENT_DIR))
+}
+
+/// The source the content index is built from.
+pub fn content_source() -> Arc<dyn ContentSource> {
+    let source = CONTENT_SOURCE.get_or_init(|| RwLock::new(default_source()));
+    match source.read() {
+        Ok(state) => state.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Directory on disk holding post images and the files the scaffolding
+/// commands write. Falls back to the configured `content_dir` for sources
+/// without one.
+pub fn local_content_dir() -> PathBuf {
+    content_source()
+        .local_root()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(content_dir)
+}
+
+/// Replaces the content source. Takes effect on the next index reload.
+pub fn replace_content_source(next: Arc<dyn ContentSource>) {
+    let source = CONTENT_SOURCE.get_or_init(|| RwLock::new(next.clone()));
+    match source.write() {
+        Ok(mut state) => *state = next,
+        Err(poisoned) => *poisoned.into_inner() = next,
+    }
+}
+
+// With `embedded-content`, the baked-in tree is used unless `content_dir` is
+// configured explicitly.
+fn default_source() -> Arc<dyn ContentSource> {
+    #[cfg(feature = "embedded-content")]
+    if site_config().content_dir.is_none() {
+        return Arc::new(EmbeddedSource::new());
+    }
+
+    Arc::new(FilesystemSource::new(content_dir()))
+}
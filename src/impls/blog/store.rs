@@ -1,32 +1,49 @@
-use crate::impls::blog::types::{Post, PostMetadata, Tag, TagDefinition};
+use crate::impls::blog::error::{line_at, line_of_field, ContentError, ContentErrorKind};
+use crate::impls::blog::front_matter::{split_front_matter, RawPostFields};
+use crate::impls::blog::index::ContentIndex;
+use crate::impls::blog::service::parse_publish_at;
+use crate::impls::blog::source::{
+    content_source, replace_content_source, ContentSignature, ContentSource,
+};
+use crate::impls::blog::translation::{mark_outdated_translations, RecordedSource};
+use crate::impls::blog::types::{
+    Post, PostMetadata, SeriesDefinition, SeriesRef, Tag, TagDefinition,
+};
+use crate::impls::blog::watcher::spawn_content_watcher;
+use crate::impls::i18n;
+use crate::impls::mermaid::contains_diagram;
 use chrono::NaiveDate;
-use serde::Deserialize;
-use std::collections::hash_map::DefaultHasher;
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::hash::{Hash, Hasher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{OnceLock, RwLock};
-use std::time::UNIX_EPOCH;
-
-const POSTS_ROOT: &str = "content/posts";
-const TAGS_PATH: &str = "content/tags.yml";
-
-#[derive(Default)]
-struct CacheState {
-    signature: u64,
-    loaded: bool,
-    posts: Vec<Post>,
+use std::sync::{Arc, OnceLock, RwLock};
+
+// Relative to the root of the content source.
+pub const POSTS_DIR: &str = "posts";
+pub const TAGS_FILE: &str = "tags.yml";
+pub const SERIES_FILE: &str = "series.yml";
+
+/// A loaded post together with the file it came from.
+pub struct SourcedPost {
+    pub path: PathBuf,
+    /// 1-based line of `path` where `post.content` starts.
+    pub body_line: usize,
+    pub post: Post,
 }
 
-fn load_tag_definitions(tags_path: &Path) -> HashMap<String, TagDefinition> {
-    let tags_text = match fs::read_to_string(tags_path) {
+fn load_tag_definitions(
+    source: &dyn ContentSource,
+    errors: &mut Vec<ContentError>,
+) -> HashMap<String, TagDefinition> {
+    let tags_path = source.display_path(Path::new(TAGS_FILE));
+    let tags_text = match source.read_to_string(Path::new(TAGS_FILE)) {
         Ok(text) => text,
         Err(error) => {
-            log::warn!(
-                "failed to read tag definitions {}: {error}",
-                tags_path.display()
-            );
+            errors.push(ContentError::new(
+                &tags_path,
+                None,
+                ContentErrorKind::Unreadable(error.to_string()),
+            ));
             return default_tag_definitions();
         }
     };
@@ -34,10 +51,11 @@ fn load_tag_definitions(tags_path: &Path) -> HashMap<String, TagDefinition> {
     let raw_config = match serde_yaml::from_str::<RawTagConfig>(&tags_text) {
         Ok(config) => config,
         Err(error) => {
-            log::warn!(
-                "failed to parse tag definitions {}: {error}",
-                tags_path.display()
-            );
+            errors.push(yaml_error(
+                &tags_path,
+                &error,
+                ContentErrorKind::InvalidDefinitions,
+            ));
             return default_tag_definitions();
         }
     };
@@ -52,7 +70,7 @@ fn load_tag_definitions(tags_path: &Path) -> HashMap<String, TagDefinition> {
             id.clone(),
             TagDefinition {
                 id,
-                labels: raw_tag.labels,
+                labels: raw_tag.labels.into_iter().collect(),
             },
         );
     }
@@ -89,151 +107,460 @@ fn default_tag_definitions() -> HashMap<String, TagDefinition> {
     ])
 }
 
-fn normalize_tag_id(value: &str) -> String {
+pub fn normalize_tag_id(value: &str) -> String {
     value.trim().to_lowercase()
 }
 
-static POSTS_CACHE: OnceLock<RwLock<CacheState>> = OnceLock::new();
+fn load_series_definitions(
+    source: &dyn ContentSource,
+    errors: &mut Vec<ContentError>,
+) -> HashMap<String, SeriesDefinition> {
+    if !source.is_file(Path::new(SERIES_FILE)) {
+        return HashMap::new();
+    }
 
-#[derive(Debug, Deserialize)]
-struct RawPostMeta {
-    slug: String,
-    date: String,
+    let series_path = source.display_path(Path::new(SERIES_FILE));
+    let series_text = match source.read_to_string(Path::new(SERIES_FILE)) {
+        Ok(text) => text,
+        Err(error) => {
+            errors.push(ContentError::new(
+                &series_path,
+                None,
+                ContentErrorKind::Unreadable(error.to_string()),
+            ));
+            return HashMap::new();
+        }
+    };
+
+    let raw_config = match serde_yaml::from_str::<RawSeriesConfig>(&series_text) {
+        Ok(config) => config,
+        Err(error) => {
+            errors.push(yaml_error(
+                &series_path,
+                &error,
+                ContentErrorKind::InvalidDefinitions,
+            ));
+            return HashMap::new();
+        }
+    };
+
+    let mut definitions = HashMap::new();
+    for raw_series in raw_config.series {
+        let id = normalize_tag_id(&raw_series.id);
+        if id.is_empty() {
+            continue;
+        }
+        definitions.insert(
+            id.clone(),
+            SeriesDefinition {
+                id,
+                titles: raw_series.titles,
+            },
+        );
+    }
+    definitions
+}
+
+static CONTENT_INDEX: OnceLock<RwLock<Arc<ContentIndex>>> = OnceLock::new();
+
+// Field precedence, lowest to highest: `meta.yml` base fields, `meta.yml`
+// `languages.{lang}` overrides, then the front matter of `{lang}.md`.
+// `translated_from` describes one language version, so it is only read from
+// `languages.{lang}` or the front matter, never from the base fields.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct RawPostMeta {
+    #[serde(flatten)]
+    pub fields: RawPostFields,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub titles: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub languages: BTreeMap<String, RawPostFields>,
+}
+
+impl RawPostMeta {
+    fn fields_for_lang(&self, lang: &str) -> RawPostFields {
+        let base = RawPostFields {
+            translated_from: None,
+            ..self.fields.clone()
+        };
+        match self.languages.get(lang) {
+            Some(overrides) => base.merged_with(overrides.clone()),
+            None => base,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct RawTagConfig {
     #[serde(default)]
-    tags: Option<Vec<String>>,
+    pub tags: Vec<RawTagDefinition>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RawTagDefinition {
+    pub id: String,
     #[serde(default)]
-    titles: HashMap<String, String>,
+    pub labels: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
-struct RawTagConfig {
+struct RawSeriesConfig {
     #[serde(default)]
-    tags: Vec<RawTagDefinition>,
+    series: Vec<RawSeriesDefinition>,
 }
 
 #[derive(Debug, Deserialize)]
-struct RawTagDefinition {
+struct RawSeriesDefinition {
     id: String,
     #[serde(default)]
-    labels: HashMap<String, String>,
+    titles: HashMap<String, String>,
 }
 
-pub fn all_posts() -> Vec<Post> {
-    let posts_root = Path::new(POSTS_ROOT);
-    let tags_path = Path::new(TAGS_PATH);
-    let signature = compute_signature(posts_root, tags_path);
-    let cache = POSTS_CACHE.get_or_init(|| RwLock::new(CacheState::default()));
-
-    if let Ok(state) = cache.read() {
-        if state.loaded && state.signature == signature {
-            return state.posts.clone();
+/// Current snapshot of the content source. The first call loads it and, for
+/// sources backed by a directory, starts the watcher that swaps in a fresh
+/// index whenever files change.
+pub fn content_index() -> Arc<ContentIndex> {
+    let mut initialized = false;
+    let index = CONTENT_INDEX.get_or_init(|| {
+        initialized = true;
+        RwLock::new(Arc::new(load_content_index()))
+    });
+    if initialized {
+        if let Some(root) = content_source().local_root() {
+            spawn_content_watcher(root, current_signature, reload_content_index);
         }
     }
-    let posts = load_posts_from_disk(posts_root, tags_path);
 
-    if let Ok(mut state) = cache.write() {
-        state.signature = signature;
-        state.loaded = true;
-        state.posts = posts.clone();
+    match index.read() {
+        Ok(state) => state.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
     }
+}
 
-    posts
+/// Switches the store to `source`, rebuilding the index if it was already
+/// loaded.
+pub fn set_content_source(source: Arc<dyn ContentSource>) {
+    replace_content_source(source);
+    reload_content_index();
 }
 
-fn load_posts_from_disk(root: &Path, tags_path: &Path) -> Vec<Post> {
-    let mut posts = Vec::new();
-    let tag_definitions = load_tag_definitions(tags_path);
+fn reload_content_index() {
+    let Some(index) = CONTENT_INDEX.get() else {
+        return;
+    };
+    let fresh = Arc::new(load_content_index());
+    log::info!("content index reloaded: {} posts", fresh.posts().count());
+    match index.write() {
+        Ok(mut state) => *state = fresh,
+        Err(poisoned) => *poisoned.into_inner() = fresh,
+    }
+}
+
+fn load_content_index() -> ContentIndex {
+    let source = content_source();
+    let signature = source.signature();
+    let (posts, errors) = load_content_from(source.as_ref());
+    for error in errors.iter() {
+        log::warn!("{error}");
+    }
+    let posts = posts.into_iter().map(|sourced| sourced.post).collect();
+    ContentIndex::build(posts, signature.hash, signature.last_modified)
+}
+
+/// Loads every post straight from the current content source together with
+/// the problems found on the way, bypassing the index. Used by `check`.
+pub fn load_content() -> (Vec<SourcedPost>, Vec<ContentError>) {
+    load_content_from(content_source().as_ref())
+}
+
+pub fn load_content_from(source: &dyn ContentSource) -> (Vec<SourcedPost>, Vec<ContentError>) {
+    let mut errors = Vec::new();
+    let posts = load_posts(source, &mut errors);
+    (posts, errors)
+}
 
-    for post_dir in list_post_dirs(root) {
-        posts.extend(load_post_dir(&post_dir, &tag_definitions));
+fn load_posts(source: &dyn ContentSource, errors: &mut Vec<ContentError>) -> Vec<SourcedPost> {
+    let mut posts = Vec::new();
+    let tag_definitions = load_tag_definitions(source, errors);
+    let series_definitions = load_series_definitions(source, errors);
+
+    for post_dir in source.list_dirs(Path::new(POSTS_DIR)) {
+        posts.extend(load_post_dir(
+            source,
+            &post_dir,
+            &tag_definitions,
+            &series_definitions,
+            errors,
+        ));
     }
 
+    let mut seen = HashMap::<(String, String), PathBuf>::new();
+    posts.retain(|sourced| {
+        let key = (
+            sourced.post.meta.slug.clone(),
+            sourced.post.meta.lang.clone(),
+        );
+        if let Some(first) = seen.get(&key) {
+            errors.push(ContentError::new(
+                &sourced.path,
+                None,
+                ContentErrorKind::DuplicateSlug {
+                    slug: key.0,
+                    lang: key.1,
+                    first: first.clone(),
+                },
+            ));
+            return false;
+        }
+        seen.insert(key, sourced.path.clone());
+        true
+    });
+
     posts.sort_by(|a, b| {
-        parse_date_or_default(&b.meta.date).cmp(&parse_date_or_default(&a.meta.date))
+        parse_date_or_default(&b.post.meta.date).cmp(&parse_date_or_default(&a.post.meta.date))
     });
     posts
 }
 
-fn load_post_dir(post_dir: &Path, tag_definitions: &HashMap<String, TagDefinition>) -> Vec<Post> {
+fn load_post_dir(
+    source: &dyn ContentSource,
+    post_dir: &Path,
+    tag_definitions: &HashMap<String, TagDefinition>,
+    series_definitions: &HashMap<String, SeriesDefinition>,
+    errors: &mut Vec<ContentError>,
+) -> Vec<SourcedPost> {
     let meta_path = post_dir.join("meta.yml");
-    let meta_text = match fs::read_to_string(&meta_path) {
-        Ok(text) => text,
-        Err(error) => {
-            log::warn!(
-                "failed to read post metadata {}: {error}",
-                meta_path.display()
-            );
-            return Vec::new();
-        }
-    };
-
-    let raw_meta = match serde_yaml::from_str::<RawPostMeta>(&meta_text) {
-        Ok(meta) => meta,
-        Err(error) => {
-            log::warn!(
-                "failed to parse post metadata {}: {error}",
-                meta_path.display()
-            );
-            return Vec::new();
-        }
+    let Some((raw_meta, meta_text)) = load_raw_meta(source, &meta_path, errors) else {
+        return Vec::new();
     };
+    let dir_slug = post_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_string();
 
     let mut posts = Vec::new();
+    let mut recorded_sources = Vec::new();
+
+    let meta_path = source.display_path(&meta_path);
+    let markdown_files = source
+        .list_files(post_dir)
+        .into_iter()
+        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("md"));
 
-    for markdown_path in list_markdown_files(post_dir) {
-        let lang = match markdown_path.file_stem().and_then(|stem| stem.to_str()) {
+    for relative_path in markdown_files {
+        let lang = match relative_path.file_stem().and_then(|stem| stem.to_str()) {
             Some(lang) => lang.to_string(),
             None => continue,
         };
 
-        let content = match fs::read_to_string(&markdown_path) {
-            Ok(content) => content,
+        let markdown_path = source.display_path(&relative_path);
+        let text = match source.read_to_string(&relative_path) {
+            Ok(text) => text,
             Err(error) => {
-                log::warn!(
-                    "failed to read markdown content {}: {error}",
-                    markdown_path.display()
-                );
+                errors.push(ContentError::new(
+                    &markdown_path,
+                    None,
+                    ContentErrorKind::Unreadable(error.to_string()),
+                ));
                 continue;
             }
         };
 
+        let (front_matter, body) = split_front_matter(&text, &markdown_path, errors);
+        // Fields may come from the front matter or meta.yml; report the first
+        // place a value is written.
+        let locate = |key: &str, value: &str| -> (PathBuf, Option<usize>) {
+            let front_matter_text = &text[..text.len() - body.len()];
+            if let Some(line) = line_of_field(front_matter_text, key, value) {
+                return (markdown_path.clone(), Some(line));
+            }
+            match line_of_field(&meta_text, key, value) {
+                Some(line) => (meta_path.clone(), Some(line)),
+                None => (markdown_path.clone(), None),
+            }
+        };
+
+        let fields = raw_meta.fields_for_lang(&lang).merged_with(front_matter);
+        let Some(date) = fields.date.clone() else {
+            errors.push(ContentError::new(
+                &markdown_path,
+                None,
+                ContentErrorKind::MissingDate { lang },
+            ));
+            continue;
+        };
+        if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+            let (path, line) = locate("date", &date);
+            errors.push(ContentError::new(
+                path,
+                line,
+                ContentErrorKind::InvalidDate {
+                    value: date.clone(),
+                },
+            ));
+        }
+
+        if let Some(publish_at) = fields
+            .publish_at
+            .as_ref()
+            .filter(|value| parse_publish_at(value).is_none())
+        {
+            let (path, line) = locate("publish_at", publish_at);
+            errors.push(ContentError::new(
+                path,
+                line,
+                ContentErrorKind::InvalidPublishAt {
+                    value: publish_at.clone(),
+                },
+            ));
+        }
+
+        let slug = fields.slug.clone().unwrap_or_else(|| dir_slug.clone());
+        let title = resolve_title(&raw_meta, &fields, &lang);
+        if title.is_none() {
+            errors.push(ContentError::new(
+                &markdown_path,
+                None,
+                ContentErrorKind::MissingTitle { lang: lang.clone() },
+            ));
+        }
+
+        let mut unknown_tags = Vec::new();
+        let tags = parse_tags(&fields.tags, tag_definitions, &lang, &mut unknown_tags);
+        for id in unknown_tags {
+            let (path, line) = locate("tags", &id);
+            errors.push(ContentError::new(
+                path,
+                line,
+                ContentErrorKind::UnknownTag { id },
+            ));
+        }
+
+        let series = parse_series(&fields, series_definitions, &lang);
+        let series_id = fields.series.as_ref().filter(|id| !id.trim().is_empty());
+        if let (None, Some(id)) = (series.as_ref(), series_id) {
+            let (path, line) = locate("series", id);
+            errors.push(ContentError::new(
+                path,
+                line,
+                ContentErrorKind::UnknownSeries { id: id.clone() },
+            ));
+        }
+
+        // A version cannot be a translation of itself.
+        if let Some(translated_from) = fields
+            .translated_from
+            .clone()
+            .filter(|source| source.lang != lang)
+        {
+            let (path, line) = locate("hash", &translated_from.hash);
+            recorded_sources.push(RecordedSource {
+                index: posts.len(),
+                source: translated_from,
+                path,
+                line,
+            });
+        }
+
+        let content = body.to_string();
         let post = Post {
             meta: PostMetadata {
-                title: resolve_title(&raw_meta, &lang),
-                date: raw_meta.date.clone(),
-                slug: raw_meta.slug.clone(),
-                tags: parse_tags(&raw_meta.tags, tag_definitions, &lang),
+                title: title.unwrap_or_else(|| slug.clone()),
+                date,
+                tags,
+                series,
+                summary: fields.summary,
+                draft: fields.draft.unwrap_or(false),
+                publish_at: fields.publish_at,
+                untranslated: fields.untranslated.unwrap_or(false),
+                outdated_from: None,
+                has_mermaid: contains_diagram(&content),
+                slug,
                 lang,
                 word_count: content.split_whitespace().count(),
             },
             content,
         };
-        posts.push(post);
+        posts.push(SourcedPost {
+            body_line: line_at(&text, text.len() - body.len()),
+            path: markdown_path.clone(),
+            post,
+        });
     }
 
+    mark_outdated_translations(&mut posts, recorded_sources, errors);
     posts
 }
 
-fn resolve_title(raw_meta: &RawPostMeta, lang: &str) -> String {
-    if let Some(title) = raw_meta.titles.get(lang) {
-        return title.clone();
+fn load_raw_meta(
+    source: &dyn ContentSource,
+    relative_path: &Path,
+    errors: &mut Vec<ContentError>,
+) -> Option<(RawPostMeta, String)> {
+    if !source.is_file(relative_path) {
+        return Some((RawPostMeta::default(), String::new()));
+    }
+
+    let meta_path = source.display_path(relative_path);
+    let meta_text = match source.read_to_string(relative_path) {
+        Ok(text) => text,
+        Err(error) => {
+            errors.push(ContentError::new(
+                &meta_path,
+                None,
+                ContentErrorKind::Unreadable(error.to_string()),
+            ));
+            return None;
+        }
+    };
+
+    match serde_yaml::from_str::<RawPostMeta>(&meta_text) {
+        Ok(meta) => Some((meta, meta_text)),
+        Err(error) => {
+            errors.push(yaml_error(
+                &meta_path,
+                &error,
+                ContentErrorKind::InvalidMeta,
+            ));
+            None
+        }
+    }
+}
+
+fn yaml_error(
+    path: &Path,
+    error: &serde_yaml::Error,
+    kind: fn(String) -> ContentErrorKind,
+) -> ContentError {
+    let line = error.location().map(|location| location.line());
+    ContentError::new(path, line, kind(error.to_string()))
+}
+
+fn resolve_title(raw_meta: &RawPostMeta, fields: &RawPostFields, lang: &str) -> Option<String> {
+    if let Some(title) = fields.title.as_ref() {
+        return Some(title.clone());
     }
 
-    if let Some(title) = raw_meta.titles.get("en") {
-        return title.clone();
+    if let Some(title) = raw_meta.titles.get(lang) {
+        return Some(title.clone());
     }
 
-    if let Some(title) = raw_meta.titles.values().next() {
-        return title.clone();
+    if let Some(title) = i18n::fallback_chain(lang)
+        .into_iter()
+        .find_map(|code| raw_meta.titles.get(code))
+    {
+        return Some(title.clone());
     }
 
-    raw_meta.slug.clone()
+    raw_meta.titles.values().next().cloned()
 }
 
 fn parse_tags(
     tags: &Option<Vec<String>>,
     tag_definitions: &HashMap<String, TagDefinition>,
     lang: &str,
+    unknown: &mut Vec<String>,
 ) -> Option<Vec<Tag>> {
     let mut parsed = Vec::new();
     let mut seen = HashSet::new();
@@ -249,7 +576,7 @@ fn parse_tags(
                     id: definition.id.clone(),
                     label: definition.label_for_lang(lang),
                 }),
-                None => log::warn!("unknown tag id '{}'", value),
+                None => unknown.push(value.clone()),
             }
         }
     }
@@ -261,94 +588,270 @@ fn parse_tags(
     }
 }
 
-fn list_post_dirs(root: &Path) -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
-    let entries = match fs::read_dir(root) {
-        Ok(entries) => entries,
-        Err(_) => return dirs,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            dirs.push(path);
-        }
+fn parse_series(
+    fields: &RawPostFields,
+    series_definitions: &HashMap<String, SeriesDefinition>,
+    lang: &str,
+) -> Option<SeriesRef> {
+    let value = fields.series.as_deref()?;
+    let series_id = normalize_tag_id(value);
+    if series_id.is_empty() {
+        return None;
     }
 
-    dirs.sort();
-    dirs
+    series_definitions
+        .get(&series_id)
+        .map(|definition| SeriesRef {
+            id: definition.id.clone(),
+            title: definition.title_for_lang(lang),
+            order: fields.series_order,
+        })
 }
 
-fn list_markdown_files(post_dir: &Path) -> Vec<PathBuf> {
-    let mut files = Vec::new();
-    let entries = match fs::read_dir(post_dir) {
-        Ok(entries) => entries,
-        Err(_) => return files,
-    };
+fn current_signature() -> ContentSignature {
+    content_source().signature()
+}
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let ext = path.extension().and_then(|value| value.to_str());
-        if path.is_file() && ext == Some("md") {
-            files.push(path);
-        }
+/// `date` as `YYYY-MM-DD`, or 1970-01-01 so undated posts sort last.
+pub fn parse_date_or_default(date: &str) -> NaiveDate {
+    if let Ok(value) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        return value;
+    }
+
+    if let Some(value) = NaiveDate::from_ymd_opt(1970, 1, 1) {
+        return value;
     }
 
-    files.sort();
-    files
+    NaiveDate::MIN
 }
 
-fn compute_signature(posts_root: &Path, tags_path: &Path) -> u64 {
-    let mut files = Vec::new();
-    collect_files_recursive(posts_root, &mut files);
-    if tags_path.is_file() {
-        files.push(tags_path.to_path_buf());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::blog::source::MemorySource;
+    use crate::impls::blog::translation::body_hash;
+
+    fn fixture() -> MemorySource {
+        let mut source = MemorySource::new();
+        source.insert(
+            TAGS_FILE,
+            "tags:\n  - id: idea\n    labels:\n      en: Idea\n      cn: 想法\n",
+        );
+        source.insert(
+            SERIES_FILE,
+            "series:\n  - id: intro\n    titles:\n      en: Introductions\n",
+        );
+        source.insert(
+            "posts/first_post/meta.yml",
+            "date: 2024-03-01\ntags:\n  - Idea\nseries: intro\nseries_order: 2\ntitles:\n  en: First post\n  cn: 第一篇\n",
+        );
+        source.insert("posts/first_post/en.md", "Hello there.\n");
+        source.insert(
+            "posts/first_post/cn.md",
+            format!(
+                "---\ntranslated_from:\n  lang: en\n  hash: {}\n---\n你好。\n",
+                body_hash("Hello there.")
+            ),
+        );
+        source.insert(
+            "posts/second/meta.yml",
+            "slug: renamed\ndate: 2024-04-01\ntitles:\n  en: Second\n",
+        );
+        source.insert("posts/second/en.md", "---\ntags: [missing]\n---\nBody\n");
+        source
     }
-    files.sort();
-
-    let mut hasher = DefaultHasher::new();
-    for file in files {
-        file.to_string_lossy().hash(&mut hasher);
-
-        if let Ok(metadata) = fs::metadata(&file) {
-            metadata.len().hash(&mut hasher);
-            if let Ok(modified) = metadata.modified() {
-                if let Ok(duration) = modified.duration_since(UNIX_EPOCH) {
-                    duration.as_secs().hash(&mut hasher);
-                    duration.subsec_nanos().hash(&mut hasher);
-                }
-            }
-        }
+
+    fn post<'a>(posts: &'a [SourcedPost], slug: &str, lang: &str) -> &'a Post {
+        &posts
+            .iter()
+            .find(|sourced| sourced.post.meta.slug == slug && sourced.post.meta.lang == lang)
+            .unwrap_or_else(|| panic!("no {slug}/{lang}"))
+            .post
     }
-    hasher.finish()
-}
 
-fn collect_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(entries) => entries,
-        Err(_) => return,
-    };
+    #[test]
+    fn slugs_come_from_meta_or_directory() {
+        let (posts, _) = load_content_from(&fixture());
+        let mut slugs: Vec<_> = posts
+            .iter()
+            .map(|sourced| {
+                (
+                    sourced.post.meta.slug.as_str(),
+                    sourced.post.meta.lang.as_str(),
+                )
+            })
+            .collect();
+        slugs.sort();
+        assert_eq!(
+            slugs,
+            [
+                ("first_post", "cn"),
+                ("first_post", "en"),
+                ("renamed", "en")
+            ]
+        );
+        assert_eq!(posts[0].post.meta.slug, "renamed");
+    }
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_files_recursive(&path, files);
-            continue;
-        }
-        if path.is_file() {
-            files.push(path);
-        }
+    #[test]
+    fn loads_each_translation_with_its_title() {
+        let (posts, errors) = load_content_from(&fixture());
+        let english = post(&posts, "first_post", "en");
+        let chinese = post(&posts, "first_post", "cn");
+        assert_eq!(english.meta.title, "First post");
+        assert_eq!(chinese.meta.title, "第一篇");
+        assert_eq!(chinese.content.trim(), "你好。");
+        assert_eq!(chinese.meta.outdated_from, None);
+        assert!(!errors
+            .iter()
+            .any(|error| matches!(error.kind, ContentErrorKind::OutdatedTranslation { .. })));
     }
-}
 
-fn parse_date_or_default(date: &str) -> NaiveDate {
-    if let Ok(value) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
-        return value;
+    #[test]
+    fn flags_translations_of_changed_sources() {
+        let mut source = fixture();
+        source.insert("posts/first_post/en.md", "Hello again.\n");
+        let (posts, errors) = load_content_from(&source);
+        assert_eq!(
+            post(&posts, "first_post", "cn")
+                .meta
+                .outdated_from
+                .as_deref(),
+            Some("en")
+        );
+        let error = errors
+            .iter()
+            .find(|error| matches!(error.kind, ContentErrorKind::OutdatedTranslation { .. }))
+            .expect("outdated translation reported");
+        assert_eq!(error.path, Path::new("posts/first_post/cn.md"));
+        assert_eq!(error.line, Some(4));
     }
 
-    if let Some(value) = NaiveDate::from_ymd_opt(1970, 1, 1) {
-        return value;
+    #[test]
+    fn reads_translated_from_only_per_language() {
+        let mut source = fixture();
+        source.insert(
+            "posts/first_post/meta.yml",
+            "date: 2024-03-01\ntranslated_from:\n  lang: en\n  hash: stale\ntitles:\n  en: First post\n  cn: 第一篇\n",
+        );
+        source.insert("posts/first_post/cn.md", "你好。\n");
+        let (posts, errors) = load_content_from(&source);
+        assert_eq!(post(&posts, "first_post", "cn").meta.outdated_from, None);
+        assert!(!errors
+            .iter()
+            .any(|error| matches!(error.kind, ContentErrorKind::OutdatedTranslation { .. })));
+
+        source.insert(
+            "posts/first_post/meta.yml",
+            "date: 2024-03-01\ntitles:\n  en: First post\nlanguages:\n  cn:\n    translated_from:\n      lang: en\n      hash: stale\n",
+        );
+        source.insert(
+            "posts/first_post/en.md",
+            "---\ntranslated_from:\n  lang: en\n  hash: stale\n---\nHello there.\n",
+        );
+        let (posts, _) = load_content_from(&source);
+        assert_eq!(
+            post(&posts, "first_post", "cn")
+                .meta
+                .outdated_from
+                .as_deref(),
+            Some("en")
+        );
+        assert_eq!(post(&posts, "first_post", "en").meta.outdated_from, None);
     }
 
-    NaiveDate::MIN
+    #[test]
+    fn resolves_tags_and_reports_unknown_ones() {
+        let (posts, errors) = load_content_from(&fixture());
+        let tags = post(&posts, "first_post", "cn").meta.tags.clone();
+        assert_eq!(
+            tags,
+            Some(vec![Tag {
+                id: "idea".to_string(),
+                label: "想法".to_string(),
+            }])
+        );
+        assert_eq!(post(&posts, "renamed", "en").meta.tags, None);
+
+        let unknown = errors
+            .iter()
+            .find(|error| {
+                error.kind
+                    == ContentErrorKind::UnknownTag {
+                        id: "missing".into(),
+                    }
+            })
+            .expect("unknown tag reported");
+        assert_eq!(unknown.path, Path::new("posts/second/en.md"));
+        assert_eq!(unknown.line, Some(2));
+    }
+
+    #[test]
+    fn locates_values_on_their_own_field() {
+        let mut source = fixture();
+        source.insert(
+            "posts/second/meta.yml",
+            "slug: renamed\ndate: 2024-04-01\ntitles:\n  en: Second missing\n",
+        );
+        source.insert(
+            "posts/second/en.md",
+            "---\nsummary: missing\ntags:\n  - missing\n---\nBody\n",
+        );
+        let (_, errors) = load_content_from(&source);
+        let unknown = errors
+            .iter()
+            .find(|error| matches!(error.kind, ContentErrorKind::UnknownTag { .. }))
+            .expect("unknown tag reported");
+        assert_eq!(unknown.path, Path::new("posts/second/en.md"));
+        assert_eq!(unknown.line, Some(4));
+    }
+
+    #[test]
+    fn reports_unparsable_publish_at() {
+        let mut source = fixture();
+        source.insert(
+            "posts/second/meta.yml",
+            "slug: renamed\ndate: 2024-04-01\npublish_at: next tuesday\ntitles:\n  en: Second\n",
+        );
+        let (_, errors) = load_content_from(&source);
+        let error = errors
+            .iter()
+            .find(|error| {
+                error.kind
+                    == ContentErrorKind::InvalidPublishAt {
+                        value: "next tuesday".to_string(),
+                    }
+            })
+            .expect("invalid publish_at reported");
+        assert_eq!(error.path, Path::new("posts/second/meta.yml"));
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn resolves_series_with_fallback_titles() {
+        let (posts, errors) = load_content_from(&fixture());
+        let expected = SeriesRef {
+            id: "intro".to_string(),
+            title: "Introductions".to_string(),
+            order: Some(2),
+        };
+        assert_eq!(
+            post(&posts, "first_post", "en").meta.series,
+            Some(expected.clone())
+        );
+        assert_eq!(post(&posts, "first_post", "cn").meta.series, Some(expected));
+
+        let mut source = fixture();
+        source.insert(
+            "posts/second/meta.yml",
+            "slug: renamed\ndate: 2024-04-01\nseries: unknown\ntitles:\n  en: Second\n",
+        );
+        let (_, errors_with_unknown) = load_content_from(&source);
+        assert!(errors_with_unknown.len() > errors.len());
+        assert!(errors_with_unknown.iter().any(|error| error.kind
+            == ContentErrorKind::UnknownSeries {
+                id: "unknown".to_string(),
+            }
+            && error.line == Some(3)));
+    }
 }
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+use crate::impls::blog::error::{ContentError, ContentErrorKind};
+use crate::impls::blog::store::SourcedPost;
+
+/// The source revision a translation was made from, written as
+/// `translated_from: { lang: cn, hash: ... }` in front matter or `meta.yml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TranslationSource {
+    pub lang: String,
+    pub hash: String,
+}
+
+impl TranslationSource {
+    pub fn of(lang: &str, body: &str) -> Self {
+        Self {
+            lang: lang.to_string(),
+            hash: body_hash(body),
+        }
+    }
+}
+
+/// A post's `translated_from`, with where it was written for diagnostics.
+pub struct RecordedSource {
+    /// Index of the translated post in the directory's post list.
+    pub index: usize,
+    pub source: TranslationSource,
+    pub path: PathBuf,
+    pub line: Option<usize>,
+}
+
+/// Revision hash of a markdown body. Only the text matters: line endings and
+/// surrounding blank lines are normalized away.
+pub fn body_hash(body: &str) -> String {
+    let normalized = body.replace("\r\n", "\n");
+    let digest = Sha256::digest(normalized.trim().as_bytes());
+    digest[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Flags translations whose source language body no longer matches the
+/// recorded hash. `posts` holds every language version of one post.
+pub fn mark_outdated_translations(
+    posts: &mut [SourcedPost],
+    recorded: Vec<RecordedSource>,
+    errors: &mut Vec<ContentError>,
+) {
+    for entry in recorded {
+        let Some(current) = posts
+            .iter()
+            .find(|sourced| sourced.post.meta.lang == entry.source.lang)
+            .map(|sourced| body_hash(&sourced.post.content))
+        else {
+            continue;
+        };
+        if current == entry.source.hash {
+            continue;
+        }
+
+        posts[entry.index].post.meta.outdated_from = Some(entry.source.lang.clone());
+        errors.push(ContentError::new(
+            entry.path,
+            entry.line,
+            ContentErrorKind::OutdatedTranslation {
+                source_lang: entry.source.lang,
+                recorded: entry.source.hash,
+                current,
+            },
+        ));
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
+use crate::impls::i18n;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagDefinition {
     pub id: String,
@@ -11,19 +13,37 @@ pub struct TagDefinition {
 
 impl TagDefinition {
     pub fn label_for_lang(&self, lang: &str) -> String {
-        if let Some(label) = self.labels.get(lang) {
-            return label.clone();
-        }
-        if let Some(label) = self.labels.get("en") {
-            return label.clone();
-        }
-        if let Some(label) = self.labels.values().next() {
-            return label.clone();
-        }
-        self.id.clone()
+        localized_text(&self.labels, lang).unwrap_or_else(|| self.id.clone())
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeriesDefinition {
+    pub id: String,
+    #[serde(default)]
+    pub titles: HashMap<String, String>,
+}
+
+impl SeriesDefinition {
+    pub fn title_for_lang(&self, lang: &str) -> String {
+        localized_text(&self.titles, lang).unwrap_or_else(|| self.id.clone())
+    }
+}
+
+/// The text for `lang`, else for the first locale of its fallback chain that
+/// has one, else any.
+fn localized_text(values: &HashMap<String, String>, lang: &str) -> Option<String> {
+    values
+        .get(lang)
+        .or_else(|| {
+            i18n::fallback_chain(lang)
+                .into_iter()
+                .find_map(|code| values.get(code))
+        })
+        .or_else(|| values.values().next())
+        .cloned()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Tag {
     pub id: String,
@@ -36,6 +56,13 @@ impl fmt::Display for Tag {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesRef {
+    pub id: String,
+    pub title: String,
+    pub order: Option<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PostMetadata {
     pub title: String,
@@ -43,6 +70,22 @@ pub struct PostMetadata {
     pub slug: String,
     pub lang: String,
     pub tags: Option<Vec<Tag>>,
+    #[serde(default)]
+    pub series: Option<SeriesRef>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub publish_at: Option<String>,
+    #[serde(default)]
+    pub untranslated: bool,
+    /// Language this translation was made from, set when that version has
+    /// changed since.
+    #[serde(default)]
+    pub outdated_from: Option<String>,
+    #[serde(default)]
+    pub has_mermaid: bool,
     pub word_count: usize,
 }
 
@@ -52,8 +95,49 @@ pub struct Post {
     pub content: String,
 }
 
+/// A post looked up for `requested_lang`. `served_lang` differs from it when
+/// the post has no translation in that language and a fallback was served.
+/// The server holds `P = Arc<Post>` from the content index and renders it into
+/// a `Post` at the server function boundary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServedPost<P = Post> {
+    pub post: P,
+    pub requested_lang: String,
+    pub served_lang: String,
+    /// Every language the post is published in.
+    pub available_langs: Vec<String>,
+}
+
+impl<P> ServedPost<P> {
+    pub fn is_fallback(&self) -> bool {
+        self.served_lang != self.requested_lang
+    }
+
+    pub fn map_post<Q>(self, map: impl FnOnce(P) -> Q) -> ServedPost<Q> {
+        ServedPost {
+            post: map(self.post),
+            requested_lang: self.requested_lang,
+            served_lang: self.served_lang,
+            available_langs: self.available_langs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostNeighbors {
+    pub previous: Option<PostMetadata>,
+    pub next: Option<PostMetadata>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagGroup {
     pub tag: Tag,
     pub posts: Vec<PostMetadata>,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    pub id: String,
+    pub title: String,
+    pub posts: Vec<PostMetadata>,
+}
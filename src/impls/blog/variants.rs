@@ -0,0 +1,347 @@
+use image::codecs::avif::AvifEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::impls::blog::assets::post_asset_path;
+use crate::impls::blog::dimensions::image_dimensions;
+use crate::impls::blog::source::{content_source, ContentSource};
+
+/// Widths generated for raster images; the original width is always offered too.
+pub const VARIANT_WIDTHS: [u32; 3] = [480, 960, 1600];
+pub const VARIANT_SIZES: &str = "(min-width: 1024px) 960px, 100vw";
+
+const CACHE_DIR_ENV: &str = "IMAGE_CACHE_DIR";
+const DEFAULT_CACHE_DIR: &str = ".cache/post-images";
+const RASTER_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+// The `image` crate only encodes lossless WebP, which comes out larger than
+// a JPEG photo; lossy sources get AVIF only.
+const LOSSLESS_EXTENSIONS: [&str; 1] = ["png"];
+const AVIF_SPEED: u8 = 8;
+const AVIF_QUALITY: u8 = 70;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VariantFormat {
+    Original,
+    Webp,
+    Avif,
+}
+
+struct VariantRequest {
+    stem: String,
+    width: u32,
+    extension: String,
+}
+
+pub struct ResponsiveSources {
+    pub srcset: String,
+    /// Only for lossless sources, see [`LOSSLESS_EXTENSIONS`].
+    pub webp_srcset: Option<String>,
+    pub avif_srcset: String,
+}
+
+static PARTIAL_COUNTER: AtomicU64 = AtomicU64::new(0);
+/// One lock per variant being encoded, so concurrent requests for the same
+/// file wait for a single encode instead of racing each other.
+static ENCODING: OnceLock<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> = OnceLock::new();
+
+/// `srcset` strings for a raster post image at `/blog/{slug}/img/{file}`.
+/// Variant files are named `{stem}-{width}w.{ext}` and generated on demand.
+pub fn responsive_sources(slug: &str, file: &str, width: u32) -> Option<ResponsiveSources> {
+    let (stem, extension) = file.rsplit_once('.')?;
+    if !is_raster_extension(extension) {
+        return None;
+    }
+
+    let mut widths = VARIANT_WIDTHS
+        .into_iter()
+        .filter(|value| *value < width)
+        .collect::<Vec<_>>();
+    widths.push(width);
+
+    let srcset = |extension: &str| {
+        widths
+            .iter()
+            .map(|value| format!("/blog/{slug}/img/{stem}-{value}w.{extension} {value}w"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    Some(ResponsiveSources {
+        srcset: srcset(extension),
+        webp_srcset: is_lossless_extension(extension).then(|| srcset("webp")),
+        avif_srcset: srcset("avif"),
+    })
+}
+
+/// Returns the cached variant for `file`, encoding it first when missing or
+/// older than its source. `None` when `file` is not a valid variant name.
+pub async fn ensure_variant(slug: &str, file: &str) -> Option<PathBuf> {
+    let request = parse_variant_file(file)?;
+    let format = variant_format(&request.extension)?;
+    let content = content_source();
+    let source = find_source(
+        content.as_ref(),
+        slug,
+        &request.stem,
+        format,
+        &request.extension,
+    )?;
+
+    let source_width = image_dimensions(&source)?.width;
+    if request.width > source_width
+        || (request.width != source_width && !VARIANT_WIDTHS.contains(&request.width))
+    {
+        return None;
+    }
+
+    let target = cache_dir().join(slug).join(file);
+    let source_modified = content.modified(&source);
+    if is_fresh(&target, source_modified) {
+        return Some(target);
+    }
+
+    let lock = encoding_lock(&target);
+    let guard = lock.lock().await;
+    // Another request may have encoded it while this one waited.
+    let generated = if is_fresh(&target, source_modified) {
+        Ok(Ok(()))
+    } else {
+        let width = request.width;
+        let output = target.clone();
+        tokio::task::spawn_blocking(move || {
+            encode_variant(
+                content.as_ref(),
+                &source,
+                &output,
+                width,
+                source_width,
+                format,
+            )
+        })
+        .await
+    };
+    drop(guard);
+    release_encoding_lock(&target, lock);
+
+    match generated {
+        Ok(Ok(())) => Some(target),
+        Ok(Err(error)) => {
+            log::warn!("failed to generate image variant {slug}/{file}: {error}");
+            None
+        }
+        Err(error) => {
+            log::warn!("image variant task failed for {slug}/{file}: {error}");
+            None
+        }
+    }
+}
+
+fn encoding_lock(target: &Path) -> Arc<tokio::sync::Mutex<()>> {
+    let locks = ENCODING.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut locks = locks.lock().unwrap_or_else(|error| error.into_inner());
+    locks.entry(target.to_path_buf()).or_default().clone()
+}
+
+fn release_encoding_lock(target: &Path, lock: Arc<tokio::sync::Mutex<()>>) {
+    let Some(locks) = ENCODING.get() else {
+        return;
+    };
+    let mut locks = locks.lock().unwrap_or_else(|error| error.into_inner());
+    // Two references left: the map's and ours, so nobody else is waiting.
+    if Arc::strong_count(&lock) <= 2 {
+        locks.remove(target);
+    }
+}
+
+fn parse_variant_file(file: &str) -> Option<VariantRequest> {
+    let (name, extension) = file.rsplit_once('.')?;
+    let (stem, width) = name.rsplit_once('-')?;
+    let width = width.strip_suffix('w')?.parse::<u32>().ok()?;
+    if stem.is_empty() || width == 0 {
+        return None;
+    }
+
+    Some(VariantRequest {
+        stem: stem.to_string(),
+        width,
+        extension: extension.to_ascii_lowercase(),
+    })
+}
+
+fn variant_format(extension: &str) -> Option<VariantFormat> {
+    match extension {
+        "webp" => Some(VariantFormat::Webp),
+        "avif" => Some(VariantFormat::Avif),
+        value if is_raster_extension(value) => Some(VariantFormat::Original),
+        _ => None,
+    }
+}
+
+// AVIF variants may come from any raster source and WebP ones from lossless
+// sources; resized originals must keep their own extension.
+fn find_source(
+    content: &dyn ContentSource,
+    slug: &str,
+    stem: &str,
+    format: VariantFormat,
+    extension: &str,
+) -> Option<PathBuf> {
+    let candidates = match format {
+        VariantFormat::Original => vec![extension],
+        VariantFormat::Webp => LOSSLESS_EXTENSIONS.to_vec(),
+        VariantFormat::Avif => RASTER_EXTENSIONS.to_vec(),
+    };
+    candidates
+        .into_iter()
+        .filter_map(|candidate| post_asset_path(slug, &format!("{stem}.{candidate}")))
+        .find(|path| content.is_file(path))
+}
+
+fn is_raster_extension(extension: &str) -> bool {
+    RASTER_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+}
+
+fn is_lossless_extension(extension: &str) -> bool {
+    LOSSLESS_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+}
+
+/// Where generated variants, and copies of images from sources that are not
+/// on disk, are kept.
+pub fn cache_dir() -> PathBuf {
+    std::env::var(CACHE_DIR_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR))
+}
+
+/// Whether the cached `target` is at least as new as its source. Sources
+/// that do not track modification times never change while running.
+pub fn is_fresh(target: &Path, source_modified: Option<SystemTime>) -> bool {
+    let Ok(target_modified) = fs::metadata(target).and_then(|metadata| metadata.modified()) else {
+        return false;
+    };
+    source_modified.is_none_or(|source| target_modified >= source)
+}
+
+/// Writes `target` through a uniquely named file next to it and renames it
+/// into place, so concurrent readers never see a partially written file.
+pub fn write_atomically(
+    target: &Path,
+    write: impl FnOnce(&Path) -> Result<(), String>,
+) -> Result<(), String> {
+    let partial = target.with_extension(format!(
+        "{}-{}.partial",
+        std::process::id(),
+        PARTIAL_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(error) = write(&partial) {
+        let _ = fs::remove_file(&partial);
+        return Err(error);
+    }
+    fs::rename(&partial, target).map_err(|error| {
+        let _ = fs::remove_file(&partial);
+        error.to_string()
+    })
+}
+
+fn encode_variant(
+    content: &dyn ContentSource,
+    source: &Path,
+    target: &Path,
+    width: u32,
+    source_width: u32,
+    format: VariantFormat,
+) -> Result<(), String> {
+    let bytes = content
+        .read_bytes(source)
+        .map_err(|error| error.to_string())?;
+    let image_format = ImageFormat::from_path(source).map_err(|error| error.to_string())?;
+    let image = image::load_from_memory_with_format(&bytes, image_format)
+        .map_err(|error| error.to_string())?;
+    let image = if width < source_width {
+        image.resize(width, u32::MAX, FilterType::Lanczos3)
+    } else {
+        image
+    };
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+
+    write_atomically(target, |partial| {
+        write_image(&image, image_format, partial, format)
+    })
+}
+
+fn write_image(
+    image: &DynamicImage,
+    source_format: ImageFormat,
+    target: &Path,
+    format: VariantFormat,
+) -> Result<(), String> {
+    let file = File::create(target).map_err(|error| error.to_string())?;
+    let mut writer = BufWriter::new(file);
+    let result = match format {
+        VariantFormat::Original => image.write_to(&mut writer, source_format),
+        VariantFormat::Webp => image
+            .to_rgba8()
+            .write_with_encoder(WebPEncoder::new_lossless(&mut writer)),
+        VariantFormat::Avif => {
+            image
+                .to_rgba8()
+                .write_with_encoder(AvifEncoder::new_with_speed_quality(
+                    &mut writer,
+                    AVIF_SPEED,
+                    AVIF_QUALITY,
+                ))
+        }
+    };
+
+    result.map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variant_file_names() {
+        let request = parse_variant_file("cover-photo-960w.WEBP").unwrap();
+        assert_eq!(request.stem, "cover-photo");
+        assert_eq!(request.width, 960);
+        assert_eq!(request.extension, "webp");
+
+        assert!(parse_variant_file("cover.png").is_none());
+        assert!(parse_variant_file("cover-0w.png").is_none());
+        assert!(parse_variant_file("-960w.png").is_none());
+    }
+
+    #[test]
+    fn offers_widths_below_the_original() {
+        let sources = responsive_sources("post", "diagram.png", 1000).unwrap();
+        assert_eq!(
+            sources.srcset,
+            "/blog/post/img/diagram-480w.png 480w, /blog/post/img/diagram-960w.png 960w, \
+             /blog/post/img/diagram-1000w.png 1000w"
+        );
+        assert!(sources.avif_srcset.contains("diagram-960w.avif 960w"));
+        assert!(sources.webp_srcset.is_some());
+    }
+
+    #[test]
+    fn skips_webp_for_lossy_sources() {
+        let sources = responsive_sources("post", "photo.jpg", 2000).unwrap();
+        assert!(sources.webp_srcset.is_none());
+        assert!(sources.avif_srcset.contains("photo-1600w.avif 1600w"));
+        assert!(responsive_sources("post", "icon.svg", 64).is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// Editors often write a file in several steps; wait for the burst to settle.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Calls `reload` on a background thread whenever anything under `root`
+/// changes. Falls back to polling `signature` when no native watcher is
+/// available (e.g. inotify limits or unsupported filesystems).
+pub fn spawn_content_watcher<S>(root: &Path, signature: fn() -> S, reload: fn())
+where
+    S: PartialEq + Send + 'static,
+{
+    let root = root.to_path_buf();
+    let spawned = thread::Builder::new()
+        .name("content-watcher".to_string())
+        .spawn(move || match watch_events(&root) {
+            Ok((watcher, events)) => {
+                log::info!("watching {} for content changes", root.display());
+                run_event_loop(watcher, events, reload);
+            }
+            Err(error) => {
+                log::warn!(
+                    "content watcher unavailable for {} ({error}); polling every {}s",
+                    root.display(),
+                    POLL_INTERVAL.as_secs()
+                );
+                run_poll_loop(signature, reload);
+            }
+        });
+
+    if let Err(error) = spawned {
+        log::warn!("failed to start content watcher thread: {error}");
+    }
+}
+
+type EventReceiver = Receiver<notify::Result<Event>>;
+
+fn watch_events(root: &Path) -> notify::Result<(RecommendedWatcher, EventReceiver)> {
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    Ok((watcher, receiver))
+}
+
+// `_watcher` is held so the OS watch stays registered for the thread's lifetime.
+fn run_event_loop(_watcher: RecommendedWatcher, events: EventReceiver, reload: fn()) {
+    while let Ok(event) = events.recv() {
+        if !is_content_change(&event) {
+            continue;
+        }
+
+        loop {
+            match events.recv_timeout(DEBOUNCE) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        reload();
+    }
+}
+
+fn run_poll_loop<S: PartialEq>(signature: fn() -> S, reload: fn()) {
+    let mut last = signature();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = signature();
+        if current != last {
+            last = current;
+            reload();
+        }
+    }
+}
+
+fn is_content_change(event: &notify::Result<Event>) -> bool {
+    match event {
+        Ok(event) => !matches!(event.kind, EventKind::Access(_)),
+        Err(error) => {
+            log::warn!("content watcher error: {error}");
+            false
+        }
+    }
+}
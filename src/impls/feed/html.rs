@@ -0,0 +1,316 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use std::collections::HashMap;
+
+use crate::impls::blog::{self, Post};
+use crate::impls::math::{prerender_math, MathTarget};
+use crate::impls::mermaid::{preformat_diagrams, prerender_diagrams};
+use crate::impls::site::{escape_xml, site_config};
+use crate::impls::theme::resolve_theme;
+use crate::utils::url::percent_decode;
+
+/// Custom components registered with the markdown renderer. Feed readers
+/// run no scripts and know none of them, so they are lowered to plain HTML.
+const COMPONENTS: [&str; 9] = [
+    "Mermaid",
+    "PostImage",
+    "Math",
+    "CodeRunner",
+    "SocialLink",
+    "social_link",
+    "IncrementCounter",
+    "ColorPicker",
+    "TestHear",
+];
+
+/// A component tag as written in the markdown, attribute values decoded.
+struct ComponentTag<'a> {
+    name: &'a str,
+    closing: bool,
+    attributes: HashMap<&'a str, String>,
+}
+
+pub fn render_post_html(post: &Post, site_url: &str) -> String {
+    let asset_base = format!("{site_url}/blog/{}/", post.meta.slug);
+    let content = blog::rewrite_post_links(&post.content, &post.meta.slug);
+    let content = preformat_diagrams(&prerender_diagrams(&content));
+    let content = prerender_math(&content, MathTarget::Html);
+    let events = Parser::new_ext(&content, markdown_options()).map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: absolutize_url(dest_url, site_url, &asset_base),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: absolutize_url(dest_url, site_url, &asset_base),
+            title,
+            id,
+        }),
+        Event::Html(text) => Event::Html(lower_components(&text, site_url, &asset_base).into()),
+        Event::InlineHtml(text) => {
+            Event::InlineHtml(lower_components(&text, site_url, &asset_base).into())
+        }
+        other => other,
+    });
+
+    let mut output = String::with_capacity(post.content.len() * 3 / 2);
+    html::push_html(&mut output, events);
+    output
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+}
+
+fn absolutize_url<'a>(url: CowStr<'a>, site_url: &str, asset_base: &str) -> CowStr<'a> {
+    match absolute_url(&url, site_url, asset_base) {
+        Some(absolute) => CowStr::from(absolute),
+        None => url,
+    }
+}
+
+/// `url` resolved against the site, or `None` when it is already absolute.
+fn absolute_url(url: &str, site_url: &str, asset_base: &str) -> Option<String> {
+    if let Some(relative) = url.strip_prefix("./") {
+        return Some(format!("{asset_base}{relative}"));
+    }
+    if url.starts_with('/') && !url.starts_with("//") {
+        return Some(format!("{site_url}{url}"));
+    }
+    None
+}
+
+/// Replaces every component tag in a chunk of raw HTML with its plain HTML
+/// equivalent. Other markup is kept as written.
+fn lower_components(html: &str, site_url: &str, asset_base: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match parse_component(tail) {
+            Some((tag, len)) => {
+                output.push_str(&render_component(&tag, site_url, asset_base));
+                rest = &tail[len..];
+            }
+            None => {
+                output.push('<');
+                rest = &tail[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Parses a component tag at the start of `text`, returning it with its
+/// length in bytes.
+fn parse_component(text: &str) -> Option<(ComponentTag<'_>, usize)> {
+    let after_open = text.strip_prefix('<')?;
+    let (closing, after_slash) = match after_open.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, after_open),
+    };
+    let name_len = after_slash
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+        .unwrap_or(after_slash.len());
+    let name = &after_slash[..name_len];
+    if !COMPONENTS.contains(&name) {
+        return None;
+    }
+
+    let mut attributes = HashMap::new();
+    let mut rest = &after_slash[name_len..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>").or_else(|| rest.strip_prefix('>')) {
+            let tag = ComponentTag {
+                name,
+                closing,
+                attributes,
+            };
+            return Some((tag, text.len() - after.len()));
+        }
+
+        let key_len =
+            rest.find(|ch: char| ch == '=' || ch == '>' || ch == '/' || ch.is_whitespace())?;
+        if key_len == 0 {
+            return None;
+        }
+        let key = &rest[..key_len];
+        rest = rest[key_len..].trim_start();
+        let Some(value) = rest.strip_prefix('=') else {
+            attributes.insert(key, String::new());
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|ch| *ch == '"' || *ch == '\'')?;
+        let end = value[1..].find(quote)? + 1;
+        attributes.insert(key, percent_decode(&value[1..end]));
+        rest = &value[end + 1..];
+    }
+}
+
+fn render_component(tag: &ComponentTag<'_>, site_url: &str, asset_base: &str) -> String {
+    if tag.closing {
+        return String::new();
+    }
+    let attribute = |name: &str| tag.attributes.get(name).map(String::as_str);
+    match tag.name {
+        "PostImage" => {
+            let src = attribute("src").unwrap_or_default();
+            let src = absolute_url(src, site_url, asset_base).unwrap_or_else(|| src.to_string());
+            let mut img = format!(
+                r#"<img src="{}" alt="{}""#,
+                escape_xml(&src),
+                escape_xml(attribute("alt").unwrap_or_default())
+            );
+            for name in ["title", "width", "height", "sizes"] {
+                if let Some(value) = attribute(name) {
+                    img.push_str(&format!(r#" {name}="{}""#, escape_xml(value)));
+                }
+            }
+            if let Some(srcset) = attribute("srcset") {
+                let srcset = absolute_srcset(srcset, site_url, asset_base);
+                img.push_str(&format!(r#" srcset="{}""#, escape_xml(&srcset)));
+            }
+            img.push_str(" />");
+            img
+        }
+        "Mermaid" => {
+            let mermaid_theme = resolve_theme(None, None).mermaid_theme;
+            let svg = attribute("svgs")
+                .and_then(|svgs| serde_json::from_str::<HashMap<String, String>>(svgs).ok())
+                .and_then(|mut svgs| svgs.remove(mermaid_theme));
+            match (svg, attribute("source").or(attribute("content"))) {
+                (Some(svg), _) => format!(r#"<div class="mermaid">{svg}</div>"#),
+                (None, Some(definition)) => {
+                    format!(r#"<pre class="mermaid">{}</pre>"#, escape_xml(definition))
+                }
+                (None, None) => String::new(),
+            }
+        }
+        "Math" => {
+            let mathml = attribute("mathml").unwrap_or_default();
+            match attribute("display") {
+                Some("block") => format!("<div>{mathml}</div>"),
+                _ => mathml.to_string(),
+            }
+        }
+        "CodeRunner" => format!(
+            r#"<pre><code class="language-{}">{}</code></pre>"#,
+            escape_xml(attribute("language").unwrap_or("rust")),
+            escape_xml(attribute("code").unwrap_or_default())
+        ),
+        "SocialLink" | "social_link" => site_config()
+            .info
+            .social_links
+            .iter()
+            .map(|link| {
+                let url = escape_xml(&link.url);
+                format!(r#"<a href="{url}">{url}</a>"#)
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        // Interactive widgets have nothing to show without scripts.
+        _ => String::new(),
+    }
+}
+
+/// `srcset` with each candidate's URL resolved against the site.
+fn absolute_srcset(srcset: &str, site_url: &str, asset_base: &str) -> String {
+    srcset
+        .split(',')
+        .map(|candidate| {
+            let candidate = candidate.trim();
+            let (url, descriptor) = candidate.split_once(' ').unwrap_or((candidate, ""));
+            let url = absolute_url(url, site_url, asset_base).unwrap_or_else(|| url.to_string());
+            format!("{url} {descriptor}").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITE: &str = "https://example.com";
+    const BASE: &str = "https://example.com/blog/post/";
+
+    #[test]
+    fn lowers_post_images_to_plain_images() {
+        let tag = r#"<PostImage src="/blog/post/img/a.png" alt="A %22quoted%22 %3Cimage%3E" width="640" srcset="/blog/post/img/a-480w.webp 480w, /blog/post/img/a-960w.webp 960w" avif_srcset="/blog/post/img/a-480w.avif 480w"/>"#;
+        assert_eq!(
+            lower_components(tag, SITE, BASE),
+            r#"<img src="https://example.com/blog/post/img/a.png" alt="A &quot;quoted&quot; &lt;image&gt;" width="640" srcset="https://example.com/blog/post/img/a-480w.webp 480w, https://example.com/blog/post/img/a-960w.webp 960w" />"#
+        );
+    }
+
+    #[test]
+    fn lowers_diagrams_to_their_svg_or_source() {
+        let svgs = format!(
+            r#"{{"{}":"<svg id=\"light\"></svg>","other":"<svg></svg>"}}"#,
+            resolve_theme(None, None).mermaid_theme
+        );
+        let tag = format!(
+            r#"<Mermaid source="graph TD" svgs="{}"/>"#,
+            svgs.replace('"', "%22")
+                .replace('<', "%3C")
+                .replace('>', "%3E")
+        );
+        assert_eq!(
+            lower_components(&tag, SITE, BASE),
+            r#"<div class="mermaid"><svg id="light"></svg></div>"#
+        );
+        assert_eq!(
+            lower_components(r#"<Mermaid content="a --%3E b"/>"#, SITE, BASE),
+            r#"<pre class="mermaid">a --&gt; b</pre>"#
+        );
+        assert_eq!(
+            preformat_diagrams("<Mermaid>\ngraph TD\n  a --> b\n</Mermaid>\n"),
+            "<pre class=\"mermaid\">graph TD\n  a --&gt; b</pre>\n"
+        );
+    }
+
+    #[test]
+    fn drops_interactive_components_and_keeps_other_markup() {
+        assert_eq!(
+            lower_components(
+                r#"<div><IncrementCounter label="x"/><ColorPicker color="#fff"></ColorPicker><span>kept</span></div>"#,
+                SITE,
+                BASE
+            ),
+            "<div><span>kept</span></div>"
+        );
+        assert_eq!(
+            lower_components(
+                r#"<CodeRunner code="a %3C b" language="rust"/>"#,
+                SITE,
+                BASE
+            ),
+            r#"<pre><code class="language-rust">a &lt; b</code></pre>"#
+        );
+        assert_eq!(
+            lower_components("<Mermaidish/>", SITE, BASE),
+            "<Mermaidish/>"
+        );
+    }
+}
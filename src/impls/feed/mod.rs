@@ -0,0 +1,11 @@
+#[cfg(feature = "server")]
+mod html;
+#[cfg(feature = "server")]
+mod routes;
+#[cfg(feature = "server")]
+mod service;
+
+#[cfg(feature = "server")]
+pub use routes::router;
+#[cfg(feature = "server")]
+pub use service::get_feed_documents;
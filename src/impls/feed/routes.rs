@@ -0,0 +1,54 @@
+use axum::extract::Path;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+
+use crate::impls::feed::service::{build_feed, FeedFormat};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/feed/{file}", get(lang_feed))
+        .route("/feed/{lang}/atom.xml", get(lang_atom_feed))
+        .route("/feed/{lang}/tags/{file}", get(tag_feed))
+        .route("/feed/{lang}/tags/{tag}/atom.xml", get(tag_atom_feed))
+}
+
+async fn lang_feed(Path(file): Path<String>) -> Response {
+    match split_feed_file(&file) {
+        Some((lang, format)) => feed_response(format, lang, None),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn lang_atom_feed(Path(lang): Path<String>) -> Response {
+    feed_response(FeedFormat::Atom, &lang, None)
+}
+
+async fn tag_feed(Path((lang, file)): Path<(String, String)>) -> Response {
+    match split_feed_file(&file) {
+        Some((tag, format)) => feed_response(format, &lang, Some(tag)),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn tag_atom_feed(Path((lang, tag)): Path<(String, String)>) -> Response {
+    feed_response(FeedFormat::Atom, &lang, Some(&tag))
+}
+
+fn feed_response(format: FeedFormat, lang: &str, tag: Option<&str>) -> Response {
+    match build_feed(format, lang, tag) {
+        Some(body) => ([(header::CONTENT_TYPE, format.content_type())], body).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn split_feed_file(file: &str) -> Option<(&str, FeedFormat)> {
+    if let Some(stem) = file.strip_suffix(".xml") {
+        return Some((stem, FeedFormat::Rss));
+    }
+    if let Some(stem) = file.strip_suffix(".json") {
+        return Some((stem, FeedFormat::Json));
+    }
+    None
+}
@@ -0,0 +1,487 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::impls::blog::{self, Post, PostMetadata};
+use crate::impls::feed::html::render_post_html;
+use crate::impls::site::{escape_xml, hreflang, site_title, site_url};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    pub const ALL: [FeedFormat; 3] = [Self::Rss, Self::Atom, Self::Json];
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+struct FeedEntry {
+    meta: PostMetadata,
+    url: String,
+    published: DateTime<Utc>,
+    content_html: String,
+}
+
+struct FeedChannel {
+    title: String,
+    home_url: String,
+    self_url: String,
+    lang: String,
+    updated: DateTime<Utc>,
+    entries: Vec<FeedEntry>,
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    language: &'a str,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    content_html: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    date_published: String,
+    tags: Vec<&'a str>,
+    language: &'a str,
+}
+
+pub fn build_feed(format: FeedFormat, lang: &str, tag: Option<&str>) -> Option<String> {
+    let posts = match tag {
+        Some(tag) => blog::get_posts_by_tag_and_lang(tag, lang),
+        None => blog::get_posts_by_lang(lang),
+    };
+    if posts.is_empty() {
+        return None;
+    }
+
+    let site = site_url();
+    let title = match tag {
+        Some(tag) => format!("{} #{}", site_title(), resolve_tag_label(&posts, tag)),
+        None => site_title(),
+    };
+    let entries = posts
+        .iter()
+        .filter_map(|meta| blog::get_post_by_slug_and_lang(&meta.slug, &meta.lang))
+        .map(|post| build_entry(&post, &site))
+        .collect::<Vec<_>>();
+    let updated = entries
+        .iter()
+        .map(|entry| entry.published)
+        .max()
+        .unwrap_or_default();
+
+    let channel = FeedChannel {
+        title,
+        home_url: format!("{site}/{lang}/blog/"),
+        self_url: format!("{site}{}", feed_path(format, lang, tag)),
+        lang: lang.to_string(),
+        updated,
+        entries,
+    };
+
+    match format {
+        FeedFormat::Rss => Some(render_rss(&channel)),
+        FeedFormat::Atom => Some(render_atom(&channel)),
+        FeedFormat::Json => render_json(&channel),
+    }
+}
+
+pub fn feed_path(format: FeedFormat, lang: &str, tag: Option<&str>) -> String {
+    let base = match tag {
+        Some(tag) => format!("/feed/{lang}/tags/{tag}"),
+        None => format!("/feed/{lang}"),
+    };
+    match format {
+        FeedFormat::Rss => format!("{base}.xml"),
+        FeedFormat::Atom => format!("{base}/atom.xml"),
+        FeedFormat::Json => format!("{base}.json"),
+    }
+}
+
+/// Every feed as `(path, body)`, for writing next to the statically
+/// generated pages.
+pub fn get_feed_documents() -> Vec<(String, String)> {
+    let mut documents = Vec::new();
+    for lang in blog::get_available_languages() {
+        let tag_ids = blog::get_tag_groups(&lang)
+            .into_iter()
+            .map(|group| group.tag.id)
+            .collect::<Vec<_>>();
+
+        for format in FeedFormat::ALL {
+            let tags = std::iter::once(None).chain(tag_ids.iter().map(|id| Some(id.as_str())));
+            for tag in tags {
+                if let Some(body) = build_feed(format, &lang, tag) {
+                    documents.push((feed_path(format, &lang, tag), body));
+                }
+            }
+        }
+    }
+    documents
+}
+
+fn build_entry(post: &Post, site: &str) -> FeedEntry {
+    FeedEntry {
+        url: format!("{site}/{}/blog/{}/", post.meta.lang, post.meta.slug),
+        published: parse_published(&post.meta.date),
+        meta: post.meta.clone(),
+        content_html: render_post_html(post, site),
+    }
+}
+
+fn render_rss(channel: &FeedChannel) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/">"#);
+    xml.push_str("<channel>");
+    xml.push_str(&format!("<title>{}</title>", escape_xml(&channel.title)));
+    xml.push_str(&format!("<link>{}</link>", escape_xml(&channel.home_url)));
+    xml.push_str(&format!(
+        "<description>{}</description>",
+        escape_xml(&channel.title)
+    ));
+    xml.push_str(&format!("<language>{}</language>", hreflang(&channel.lang)));
+    xml.push_str(&format!(
+        r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape_xml(&channel.self_url)
+    ));
+    xml.push_str(&format!(
+        "<lastBuildDate>{}</lastBuildDate>",
+        channel.updated.to_rfc2822()
+    ));
+
+    for entry in channel.entries.iter() {
+        xml.push_str("<item>");
+        xml.push_str(&format!("<title>{}</title>", escape_xml(&entry.meta.title)));
+        xml.push_str(&format!("<link>{}</link>", escape_xml(&entry.url)));
+        xml.push_str(&format!(
+            r#"<guid isPermaLink="true">{}</guid>"#,
+            escape_xml(&entry.url)
+        ));
+        if let Some(summary) = entry.meta.summary.as_deref() {
+            xml.push_str(&format!(
+                "<description>{}</description>",
+                escape_xml(summary)
+            ));
+        }
+        xml.push_str(&format!(
+            "<pubDate>{}</pubDate>",
+            entry.published.to_rfc2822()
+        ));
+        for label in tag_labels(&entry.meta) {
+            xml.push_str(&format!("<category>{}</category>", escape_xml(label)));
+        }
+        xml.push_str(&format!(
+            "<content:encoded>{}</content:encoded>",
+            escape_xml(&entry.content_html)
+        ));
+        xml.push_str("</item>");
+    }
+
+    xml.push_str("</channel></rss>\n");
+    xml
+}
+
+fn render_atom(channel: &FeedChannel) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(&format!(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}">"#,
+        hreflang(&channel.lang)
+    ));
+    xml.push_str(&format!("<id>{}</id>", escape_xml(&channel.self_url)));
+    xml.push_str(&format!("<title>{}</title>", escape_xml(&channel.title)));
+    xml.push_str(&format!(
+        "<updated>{}</updated>",
+        channel.updated.to_rfc3339()
+    ));
+    xml.push_str(&format!(
+        r#"<link rel="self" type="application/atom+xml" href="{}"/>"#,
+        escape_xml(&channel.self_url)
+    ));
+    xml.push_str(&format!(
+        r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+        escape_xml(&channel.home_url)
+    ));
+    xml.push_str(&format!(
+        "<author><name>{}</name></author>",
+        escape_xml(&site_title())
+    ));
+
+    for entry in channel.entries.iter() {
+        xml.push_str("<entry>");
+        xml.push_str(&format!("<id>{}</id>", escape_xml(&entry.url)));
+        xml.push_str(&format!("<title>{}</title>", escape_xml(&entry.meta.title)));
+        xml.push_str(&format!(
+            r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+            escape_xml(&entry.url)
+        ));
+        xml.push_str(&format!(
+            "<published>{}</published>",
+            entry.published.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "<updated>{}</updated>",
+            entry.published.to_rfc3339()
+        ));
+        if let Some(summary) = entry.meta.summary.as_deref() {
+            xml.push_str(&format!("<summary>{}</summary>", escape_xml(summary)));
+        }
+        for tag in entry.meta.tags.iter().flatten() {
+            xml.push_str(&format!(
+                r#"<category term="{}" label="{}"/>"#,
+                escape_xml(&tag.id),
+                escape_xml(&tag.label)
+            ));
+        }
+        xml.push_str(&format!(
+            r#"<content type="html">{}</content>"#,
+            escape_xml(&entry.content_html)
+        ));
+        xml.push_str("</entry>");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn render_json(channel: &FeedChannel) -> Option<String> {
+    let language = hreflang(&channel.lang);
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &channel.title,
+        home_page_url: &channel.home_url,
+        feed_url: &channel.self_url,
+        language: &language,
+        items: channel
+            .entries
+            .iter()
+            .map(|entry| JsonFeedItem {
+                id: &entry.url,
+                url: &entry.url,
+                title: &entry.meta.title,
+                content_html: &entry.content_html,
+                summary: entry.meta.summary.as_deref(),
+                date_published: entry.published.to_rfc3339(),
+                tags: tag_labels(&entry.meta).collect(),
+                language: &language,
+            })
+            .collect(),
+    };
+
+    match serde_json::to_string_pretty(&feed) {
+        Ok(json) => Some(json),
+        Err(error) => {
+            log::warn!(
+                "failed to serialize json feed {}: {error}",
+                channel.self_url
+            );
+            None
+        }
+    }
+}
+
+fn resolve_tag_label(posts: &[PostMetadata], tag: &str) -> String {
+    let tag_id = tag.trim().to_lowercase();
+    posts
+        .iter()
+        .flat_map(|meta| meta.tags.iter().flatten())
+        .find(|item| item.id == tag_id)
+        .map(|item| item.label.clone())
+        .unwrap_or(tag_id)
+}
+
+fn tag_labels(meta: &PostMetadata) -> impl Iterator<Item = &str> {
+    meta.tags.iter().flatten().map(|tag| tag.label.as_str())
+}
+
+fn parse_published(date: &str) -> DateTime<Utc> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|value| value.and_hms_opt(0, 0, 0))
+        .map(|value| Utc.from_utc_datetime(&value))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::blog::Tag;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use std::collections::HashMap;
+
+    const TITLE: &str = r#"Tom & "Jerry" <3"#;
+    const URL: &str = "https://example.com/en/blog/tom-&-jerry/";
+    const CONTENT: &str = "<p>a &amp; b <img src=\"https://example.com/a.png\" /></p>";
+
+    fn channel() -> FeedChannel {
+        let meta = PostMetadata {
+            title: TITLE.to_string(),
+            date: "2024-03-05".to_string(),
+            slug: "tom-&-jerry".to_string(),
+            lang: "en".to_string(),
+            tags: Some(vec![Tag {
+                id: "c".to_string(),
+                label: "C & C++".to_string(),
+            }]),
+            series: None,
+            summary: Some("1 < 2".to_string()),
+            draft: false,
+            publish_at: None,
+            untranslated: false,
+            outdated_from: None,
+            has_mermaid: false,
+            word_count: 3,
+        };
+        FeedChannel {
+            title: "Site".to_string(),
+            home_url: "https://example.com/en/blog/".to_string(),
+            self_url: "https://example.com/feed/en.xml".to_string(),
+            lang: "en".to_string(),
+            updated: parse_published(&meta.date),
+            entries: vec![FeedEntry {
+                url: URL.to_string(),
+                published: parse_published(&meta.date),
+                content_html: CONTENT.to_string(),
+                meta,
+            }],
+        }
+    }
+
+    fn published() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 5, 0, 0, 0).unwrap()
+    }
+
+    /// Every element as `(path, attributes, text)`, `path` joining the names
+    /// of its ancestors and its own with `/`.
+    fn parse_xml(xml: &str) -> Vec<(String, HashMap<String, String>, String)> {
+        let mut reader = Reader::from_str(xml);
+        let mut names = Vec::<String>::new();
+        let mut open = Vec::<(String, HashMap<String, String>, String)>::new();
+        let mut elements = Vec::new();
+        loop {
+            let event = reader.read_event().expect("well-formed xml");
+            match &event {
+                Event::Start(start) | Event::Empty(start) => {
+                    names.push(String::from_utf8_lossy(start.name().as_ref()).to_string());
+                    let attributes = start
+                        .attributes()
+                        .map(|attribute| {
+                            let attribute = attribute.expect("valid attribute");
+                            let value = attribute.unescape_value().expect("escaped value");
+                            (
+                                String::from_utf8_lossy(attribute.key.as_ref()).to_string(),
+                                value.to_string(),
+                            )
+                        })
+                        .collect();
+                    open.push((names.join("/"), attributes, String::new()));
+                }
+                Event::Text(text) => {
+                    if let Some((_, _, content)) = open.last_mut() {
+                        content.push_str(&text.unescape().expect("escaped text"));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            if matches!(event, Event::Empty(_) | Event::End(_)) {
+                names.pop();
+                elements.extend(open.pop());
+            }
+        }
+        elements
+    }
+
+    fn texts(elements: &[(String, HashMap<String, String>, String)], path: &str) -> Vec<String> {
+        elements
+            .iter()
+            .filter(|(element, _, _)| element == path)
+            .map(|(_, _, text)| text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn rss_items_link_date_and_escape_their_fields() {
+        let elements = parse_xml(&render_rss(&channel()));
+        assert_eq!(texts(&elements, "rss/channel/item/link"), [URL]);
+        assert_eq!(texts(&elements, "rss/channel/item/guid"), [URL]);
+        assert_eq!(texts(&elements, "rss/channel/item/title"), [TITLE]);
+        assert_eq!(texts(&elements, "rss/channel/item/description"), ["1 < 2"]);
+        assert_eq!(texts(&elements, "rss/channel/item/category"), ["C & C++"]);
+        assert_eq!(
+            texts(&elements, "rss/channel/item/content:encoded"),
+            [CONTENT]
+        );
+        let pub_date = &texts(&elements, "rss/channel/item/pubDate")[0];
+        assert_eq!(
+            DateTime::parse_from_rfc2822(pub_date).expect("rfc 2822 date"),
+            published()
+        );
+    }
+
+    #[test]
+    fn atom_entries_link_date_and_escape_their_fields() {
+        let elements = parse_xml(&render_atom(&channel()));
+        let links = elements
+            .iter()
+            .filter(|(path, _, _)| path == "feed/entry/link")
+            .map(|(_, attributes, _)| attributes["href"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(links, [URL]);
+        assert_eq!(texts(&elements, "feed/entry/id"), [URL]);
+        assert_eq!(texts(&elements, "feed/entry/title"), [TITLE]);
+        assert_eq!(texts(&elements, "feed/entry/content"), [CONTENT]);
+        let category = elements
+            .iter()
+            .find(|(path, _, _)| path == "feed/entry/category")
+            .map(|(_, attributes, _)| attributes["label"].as_str());
+        assert_eq!(category, Some("C & C++"));
+        for field in ["published", "updated"] {
+            let date = &texts(&elements, &format!("feed/entry/{field}"))[0];
+            assert_eq!(
+                DateTime::parse_from_rfc3339(date).expect("rfc 3339 date"),
+                published()
+            );
+        }
+    }
+
+    #[test]
+    fn json_items_link_date_and_keep_their_fields_verbatim() {
+        let json = render_json(&channel()).expect("serializable feed");
+        let feed = serde_json::from_str::<serde_json::Value>(&json).expect("valid json");
+        let item = &feed["items"][0];
+        assert_eq!(feed["items"].as_array().map(Vec::len), Some(1));
+        assert_eq!(item["id"], URL);
+        assert_eq!(item["url"], URL);
+        assert_eq!(item["title"], TITLE);
+        assert_eq!(item["content_html"], CONTENT);
+        assert_eq!(item["tags"][0], "C & C++");
+        let date = item["date_published"].as_str().expect("date string");
+        assert_eq!(
+            DateTime::parse_from_rfc3339(date).expect("rfc 3339 date"),
+            published()
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use axum::extract::Request;
+use axum::http::header::{self, HeaderMap, HeaderValue};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+use std::time::SystemTime;
+
+use crate::impls::blog;
+use crate::impls::http_cache::policy::CachePolicy;
+use crate::impls::i18n;
+use crate::impls::session::consts::SYSTEM_THEME_COOKIE_NAME;
+use crate::impls::session::preference::{self, PreferenceField};
+use crate::impls::session::AppSession;
+use crate::utils::cookie::read_cookie;
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// A post image as last hashed; a new modification time or size means the
+/// file has to be hashed again.
+#[derive(PartialEq, Eq, Hash)]
+struct AssetKey {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
+static ASSET_ETAGS: OnceLock<RwLock<HashMap<AssetKey, String>>> = OnceLock::new();
+
+/// Validators for a response, computed from what it is built from so a
+/// conditional hit is answered without rendering or reading it.
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<SystemTime>,
+}
+
+/// Adds `Cache-Control`, a strong `ETag` and `Last-Modified` to successful
+/// GET/HEAD responses, and answers matching conditional requests with
+/// `304 Not Modified` before the handler runs.
+///
+/// Runs inside the session layer: page validators depend on the visitor's
+/// preferences, and the session layer still adds its `Set-Cookie` to a 304.
+pub async fn cache_headers(request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    let policy = CachePolicy::for_path(&path);
+    let validators = policy
+        .uses_validators()
+        .then(|| validators_for(&request, policy));
+
+    if let Some(validators) = validators.as_ref() {
+        if is_not_modified(request.headers(), validators) {
+            let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+            apply_headers(not_modified.headers_mut(), policy, validators);
+            return not_modified;
+        }
+    }
+
+    let mut response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let policy =
+        if response.headers().contains_key(header::SET_COOKIE) && policy != CachePolicy::NoStore {
+            CachePolicy::Page
+        } else {
+            policy
+        };
+    match validators.as_ref() {
+        Some(validators) => apply_headers(response.headers_mut(), policy, validators),
+        None => {
+            response
+                .headers_mut()
+                .entry(header::CACHE_CONTROL)
+                .or_insert(HeaderValue::from_static(policy.cache_control()));
+        }
+    }
+    response
+}
+
+/// Fills in the caching headers a handler did not set itself; used for both
+/// full responses and 304s (RFC 9110 §15.4.5).
+fn apply_headers(headers: &mut HeaderMap, policy: CachePolicy, validators: &Validators) {
+    headers
+        .entry(header::CACHE_CONTROL)
+        .or_insert(HeaderValue::from_static(policy.cache_control()));
+    if let Some(value) = validators
+        .etag
+        .as_deref()
+        .and_then(|etag| HeaderValue::from_str(etag).ok())
+    {
+        headers.entry(header::ETAG).or_insert(value);
+    }
+    if let Some(value) = validators
+        .last_modified
+        .and_then(|time| http_date(time).ok())
+    {
+        headers.entry(header::LAST_MODIFIED).or_insert(value);
+    }
+    if policy == CachePolicy::Page {
+        headers
+            .entry(header::VARY)
+            .or_insert(HeaderValue::from_static("Cookie, Accept-Language"));
+    }
+}
+
+fn validators_for(request: &Request, policy: CachePolicy) -> Validators {
+    let path = request.uri().path();
+    if policy == CachePolicy::Asset {
+        if let Some((etag, modified)) = asset_validators(path) {
+            return Validators {
+                etag: Some(etag),
+                last_modified: Some(modified),
+            };
+        }
+    }
+
+    Validators {
+        etag: Some(content_etag(request, policy)),
+        last_modified: blog::content_last_modified(),
+    }
+}
+
+/// An ETag for responses built from the content store: it changes with the
+/// release, the content, the URL and, for pages, everything from the visitor
+/// that SSR reads. Restarting the same release keeps client caches valid.
+fn content_etag(request: &Request, policy: CachePolicy) -> String {
+    let mut hasher = Sha256::new();
+    hash_field(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+    hash_field(&mut hasher, request.uri().to_string().as_bytes());
+    for locale in i18n::enabled_locales() {
+        hash_field(
+            &mut hasher,
+            &blog::content_version(locale.code).to_be_bytes(),
+        );
+    }
+
+    if policy == CachePolicy::Page {
+        if let Some(session) = request.extensions().get::<AppSession>() {
+            for field in PreferenceField::ALL {
+                let value = preference::get(session, field).unwrap_or_default();
+                hash_field(&mut hasher, value.as_bytes());
+            }
+        }
+        let theme = read_cookie(request.headers(), SYSTEM_THEME_COOKIE_NAME).unwrap_or_default();
+        hash_field(&mut hasher, theme.as_bytes());
+        let accept_language = request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .map(HeaderValue::as_bytes)
+            .unwrap_or_default();
+        hash_field(&mut hasher, accept_language);
+    }
+    strong_etag(&hasher.finalize())
+}
+
+// Fields are length-prefixed so that moving bytes between them changes the tag.
+fn hash_field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_be_bytes());
+    hasher.update(bytes);
+}
+
+fn strong_etag(digest: &[u8]) -> String {
+    let hex = digest[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("\"{hex}\"")
+}
+
+/// Post images are served as they are in the content source, so their ETag
+/// is a hash of the file. Generated variants and sources without modification
+/// times fall back to the content ETag.
+fn asset_validators(path: &str) -> Option<(String, SystemTime)> {
+    let (slug, file) = path.strip_prefix("/blog/")?.split_once("/img/")?;
+    let asset_path = blog::post_asset_path(slug, file)?;
+    let source = blog::content_source();
+    let modified = source.modified(&asset_path)?;
+    let len = source.file_len(&asset_path)?;
+    let key = AssetKey {
+        path: asset_path,
+        modified,
+        len,
+    };
+
+    let cache = ASSET_ETAGS.get_or_init(|| RwLock::new(HashMap::new()));
+    if let Some(etag) = cache.read().ok().and_then(|state| state.get(&key).cloned()) {
+        return Some((etag, modified));
+    }
+    let bytes = source.read_bytes(&key.path).ok()?;
+    let etag = strong_etag(&Sha256::digest(&bytes));
+    if let Ok(mut state) = cache.write() {
+        state.retain(|cached, _| cached.path != key.path);
+        state.insert(key, etag.clone());
+    }
+    Some((etag, modified))
+}
+
+/// `If-None-Match` takes precedence; `If-Modified-Since` is only consulted
+/// when the request carries no entity tags (RFC 9110 §13.2.2).
+fn is_not_modified(request: &HeaderMap, validators: &Validators) -> bool {
+    if let Some(if_none_match) = request.get(header::IF_NONE_MATCH) {
+        let Some(etag) = validators.etag.as_deref() else {
+            return false;
+        };
+        return if_none_match.to_str().is_ok_and(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .any(|candidate| candidate == "*" || weak_match(candidate, etag))
+        });
+    }
+
+    let since = request
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+    let modified = validators.last_modified.map(DateTime::<Utc>::from);
+    match (since, modified) {
+        // HTTP dates have whole seconds.
+        (Some(since), Some(modified)) => modified.timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
+
+// GET revalidation uses the weak comparison, so `W/"x"` matches `"x"`.
+fn weak_match(candidate: &str, etag: &str) -> bool {
+    candidate.trim_start_matches("W/") == etag.trim_start_matches("W/")
+}
+
+fn http_date(time: SystemTime) -> Result<HeaderValue, header::InvalidHeaderValue> {
+    let time = DateTime::<Utc>::from(time);
+    HeaderValue::from_str(&time.format(HTTP_DATE_FORMAT).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(etag: &str, modified: &str) -> Validators {
+        Validators {
+            etag: Some(etag.to_string()),
+            last_modified: Some(DateTime::parse_from_rfc2822(modified).unwrap().into()),
+        }
+    }
+
+    fn request(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn matches_entity_tags_weakly() {
+        let current = validators("W/\"abc\"", "Tue, 01 Oct 2024 10:00:00 GMT");
+        assert!(is_not_modified(
+            &request(&[(header::IF_NONE_MATCH, "\"old\", \"abc\"")]),
+            &current
+        ));
+        assert!(is_not_modified(
+            &request(&[(header::IF_NONE_MATCH, "*")]),
+            &current
+        ));
+        assert!(!is_not_modified(
+            &request(&[(header::IF_NONE_MATCH, "W/\"old\"")]),
+            &current
+        ));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence_over_dates() {
+        let current = validators("W/\"abc\"", "Tue, 01 Oct 2024 10:00:00 GMT");
+        let headers = request(&[
+            (header::IF_NONE_MATCH, "W/\"old\""),
+            (header::IF_MODIFIED_SINCE, "Wed, 02 Oct 2024 10:00:00 GMT"),
+        ]);
+        assert!(!is_not_modified(&headers, &current));
+    }
+
+    #[test]
+    fn compares_modification_dates_in_seconds() {
+        let current = validators("W/\"abc\"", "Tue, 01 Oct 2024 10:00:00 GMT");
+        assert!(is_not_modified(
+            &request(&[(header::IF_MODIFIED_SINCE, "Tue, 01 Oct 2024 10:00:00 GMT")]),
+            &current
+        ));
+        assert!(!is_not_modified(
+            &request(&[(header::IF_MODIFIED_SINCE, "Tue, 01 Oct 2024 09:59:59 GMT")]),
+            &current
+        ));
+    }
+
+    #[test]
+    fn not_modified_keeps_caching_headers() {
+        let current = validators("W/\"abc\"", "Tue, 01 Oct 2024 10:00:00 GMT");
+        let mut headers = HeaderMap::new();
+        apply_headers(&mut headers, CachePolicy::Page, &current);
+        assert_eq!(headers[header::CACHE_CONTROL], "private, no-cache");
+        assert_eq!(headers[header::ETAG], "W/\"abc\"");
+        assert_eq!(
+            headers[header::LAST_MODIFIED],
+            "Tue, 01 Oct 2024 10:00:00 GMT"
+        );
+        assert_eq!(headers[header::VARY], "Cookie, Accept-Language");
+    }
+
+    #[test]
+    fn etags_are_strong_and_depend_only_on_their_inputs() {
+        let etag = strong_etag(&Sha256::digest(b"page"));
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag.len(), 34);
+        assert_eq!(etag, strong_etag(&Sha256::digest(b"page")));
+
+        let mut split = Sha256::new();
+        hash_field(&mut split, b"ab");
+        hash_field(&mut split, b"c");
+        let mut moved = Sha256::new();
+        hash_field(&mut moved, b"a");
+        hash_field(&mut moved, b"bc");
+        assert_ne!(split.finalize(), moved.finalize());
+    }
+}
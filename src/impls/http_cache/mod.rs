@@ -0,0 +1,7 @@
+#[cfg(feature = "server")]
+mod middleware;
+#[cfg(feature = "server")]
+mod policy;
+
+#[cfg(feature = "server")]
+pub use middleware::cache_headers;
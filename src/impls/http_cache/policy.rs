@@ -0,0 +1,96 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Fingerprinted bundle assets; the URL changes whenever the file does.
+    Immutable,
+    /// Post images and their generated variants.
+    Asset,
+    /// Feeds, sitemap, robots.txt and search indexes.
+    Document,
+    /// Blog JSON served to the client between navigations.
+    ShortLived,
+    /// SSR pages; they embed per-visitor locale/theme preferences.
+    Page,
+    /// Preference writes and signed previews.
+    NoStore,
+}
+
+impl CachePolicy {
+    pub fn for_path(path: &str) -> Self {
+        if path.starts_with("/assets/") || path.starts_with("/wasm/") {
+            return Self::Immutable;
+        }
+        if path.starts_with("/api/user/")
+            || path.starts_with("/api/blog/preview/")
+            || path.starts_with("/preview/")
+        {
+            return Self::NoStore;
+        }
+        if path.starts_with("/api/") {
+            return Self::ShortLived;
+        }
+        if path.starts_with("/blog/") && path.contains("/img/") {
+            return Self::Asset;
+        }
+        if path.starts_with("/feed/")
+            || path.starts_with("/search-index/")
+            || path == "/sitemap.xml"
+            || path == "/robots.txt"
+        {
+            return Self::Document;
+        }
+        Self::Page
+    }
+
+    pub fn cache_control(self) -> &'static str {
+        match self {
+            Self::Immutable => "public, max-age=31536000, immutable",
+            Self::Asset => "public, max-age=86400, stale-while-revalidate=604800",
+            Self::Document => "public, max-age=3600",
+            Self::ShortLived => "public, max-age=60",
+            Self::Page => "private, no-cache",
+            Self::NoStore => "private, no-store",
+        }
+    }
+
+    pub fn uses_validators(self) -> bool {
+        !matches!(self, Self::NoStore)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_paths() {
+        assert_eq!(
+            CachePolicy::for_path("/assets/app-1a2b.css"),
+            CachePolicy::Immutable
+        );
+        assert_eq!(
+            CachePolicy::for_path("/api/user/theme/dark"),
+            CachePolicy::NoStore
+        );
+        assert_eq!(
+            CachePolicy::for_path("/preview/post/en/token"),
+            CachePolicy::NoStore
+        );
+        assert_eq!(
+            CachePolicy::for_path("/api/blog/posts/en"),
+            CachePolicy::ShortLived
+        );
+        assert_eq!(
+            CachePolicy::for_path("/blog/post/img/cover-960w.avif"),
+            CachePolicy::Asset
+        );
+        assert_eq!(CachePolicy::for_path("/sitemap.xml"), CachePolicy::Document);
+        assert_eq!(CachePolicy::for_path("/en/blog/post/"), CachePolicy::Page);
+    }
+
+    #[test]
+    fn only_no_store_skips_validators() {
+        assert!(!CachePolicy::NoStore.uses_validators());
+        assert!(CachePolicy::Page.uses_validators());
+        assert_eq!(CachePolicy::Page.cache_control(), "private, no-cache");
+    }
+}
@@ -1,3 +0,0 @@
-layout_root_asidebar_nav_articles = Blog
-layout_root_asidebar_nav_tags = Tag
-layout_root_asidebar_nav_about = About
@@ -1,3 +0,0 @@
-layout_root_asidebar_nav_articles = 文
-layout_root_asidebar_nav_tags = 集
-layout_root_asidebar_nav_about = 我
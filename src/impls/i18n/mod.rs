@@ -1,12 +1,19 @@
+mod registry;
+
 use dioxus_i18n::prelude::*;
-use unic_langid::{langid, LanguageIdentifier};
 
-pub const DEFAULT_LANGUAGE: LanguageIdentifier = langid!("zh-CN");
-pub const EN_US_LANGUAGE: LanguageIdentifier = langid!("en-US");
+#[cfg(feature = "server")]
+pub use registry::negotiate_accept_language;
+pub use registry::{
+    default_locale, enabled_locales, fallback_chain, find_locale, resolve_locale, set_site_locales,
+};
 
 pub fn build_i18n_config() -> I18nConfig {
-    I18nConfig::new(DEFAULT_LANGUAGE)
-        .with_locale((DEFAULT_LANGUAGE, include_str!("./locales/zh-CN.ftl")))
-        .with_locale((EN_US_LANGUAGE, include_str!("./locales/en-US.ftl")))
-        .with_fallback(DEFAULT_LANGUAGE)
+    let default = default_locale().langid.clone();
+    enabled_locales()
+        .iter()
+        .fold(I18nConfig::new(default.clone()), |config, locale| {
+            config.with_locale((locale.langid.clone(), locale.ftl))
+        })
+        .with_fallback(default)
 }
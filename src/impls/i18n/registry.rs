@@ -0,0 +1,261 @@
+use std::sync::OnceLock;
+use unic_langid::{langid, LanguageIdentifier};
+
+use crate::impls::site::{LocaleInfo, SiteInfo};
+
+/// A language the site can be served in. Adding one takes a `[[locales]]`
+/// entry in `site.toml` and its messages in `locales/{tag}.ftl` under the
+/// content directory.
+#[derive(Debug, PartialEq)]
+pub struct LocaleDef {
+    /// Short code used in URLs, content file names and the session.
+    pub code: &'static str,
+    pub langid: LanguageIdentifier,
+    /// Name shown in the language switcher, written in the language itself.
+    pub name: &'static str,
+    /// Codes tried in order when content is missing in this language.
+    pub fallback: Vec<&'static str>,
+    pub ftl: &'static str,
+}
+
+impl LocaleDef {
+    /// BCP 47 tag for `lang`/`hreflang` attributes and feeds.
+    pub fn tag(&self) -> String {
+        self.langid.to_string()
+    }
+
+    // The registry lives for the whole process, so its strings are leaked
+    // once to keep handing out `&'static` codes and names.
+    fn from_info(info: &LocaleInfo) -> Option<Self> {
+        Some(Self {
+            code: leak(&info.code),
+            langid: info.tag.parse().ok()?,
+            name: leak(&info.name),
+            fallback: info.fallback.iter().map(|code| leak(code)).collect(),
+            ftl: leak(&info.ftl),
+        })
+    }
+
+    /// Only used when the settings list no usable locale, which startup
+    /// validation rules out on the server.
+    fn placeholder() -> Self {
+        Self {
+            code: "en",
+            langid: langid!("en-US"),
+            name: "English",
+            fallback: Vec::new(),
+            ftl: "",
+        }
+    }
+}
+
+fn leak(value: &str) -> &'static str {
+    Box::leak(value.to_owned().into_boxed_str())
+}
+
+struct ActiveLocales {
+    enabled: Vec<&'static LocaleDef>,
+    default: &'static LocaleDef,
+}
+
+impl ActiveLocales {
+    fn from_info(info: &SiteInfo) -> Self {
+        let mut defs = info
+            .locales
+            .iter()
+            .filter_map(LocaleDef::from_info)
+            .collect::<Vec<_>>();
+        if defs.is_empty() {
+            defs.push(LocaleDef::placeholder());
+        }
+        let enabled = Vec::leak(defs).iter().collect::<Vec<_>>();
+        let default = enabled
+            .iter()
+            .copied()
+            .find(|locale| locale.code == info.default_locale)
+            .unwrap_or(enabled[0]);
+
+        Self { enabled, default }
+    }
+
+    fn find(&self, code: &str) -> Option<&'static LocaleDef> {
+        let code = code.trim();
+        self.enabled.iter().copied().find(|locale| {
+            locale.code.eq_ignore_ascii_case(code) || locale.tag().eq_ignore_ascii_case(code)
+        })
+    }
+
+    fn fallback_chain(&self, code: &str) -> Vec<&'static str> {
+        let mut chain = Vec::new();
+        let candidates = self
+            .find(code)
+            .map(|locale| std::iter::once(locale.code).chain(locale.fallback.iter().copied()))
+            .into_iter()
+            .flatten()
+            .chain(std::iter::once(self.default.code));
+        for candidate in candidates {
+            if let Some(locale) = self.find(candidate) {
+                if !chain.contains(&locale.code) {
+                    chain.push(locale.code);
+                }
+            }
+        }
+        chain
+    }
+
+    fn negotiate(&self, header: &str) -> Option<&'static LocaleDef> {
+        let mut ranges = header
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let range = params.next()?.trim();
+                let quality = params
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |value| value.trim().parse::<f32>().ok())?;
+                (!range.is_empty() && range != "*" && quality > 0.0).then_some((range, quality))
+            })
+            .collect::<Vec<_>>();
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges.into_iter().find_map(|(range, _)| {
+            self.find(range).or_else(|| {
+                let requested = range.parse::<LanguageIdentifier>().ok()?;
+                self.enabled
+                    .iter()
+                    .copied()
+                    .find(|locale| locale.langid.language == requested.language)
+            })
+        })
+    }
+}
+
+static ACTIVE: OnceLock<ActiveLocales> = OnceLock::new();
+
+fn active() -> &'static ActiveLocales {
+    ACTIVE.get_or_init(|| ActiveLocales::from_info(&configured_site_info()))
+}
+
+#[cfg(feature = "server")]
+fn configured_site_info() -> SiteInfo {
+    crate::impls::site::site_info()
+}
+
+#[cfg(not(feature = "server"))]
+fn configured_site_info() -> SiteInfo {
+    panic!("locales were read before the site settings were fetched")
+}
+
+/// Pins the enabled locales to the site settings. The client calls this once
+/// the settings have been fetched; the server reads them from its config.
+pub fn set_site_locales(info: &SiteInfo) {
+    let _ = ACTIVE.set(ActiveLocales::from_info(info));
+}
+
+/// Looks an enabled locale up by its short code or its BCP 47 tag, ignoring
+/// case.
+pub fn find_locale(code: &str) -> Option<&'static LocaleDef> {
+    active().find(code)
+}
+
+pub fn enabled_locales() -> &'static [&'static LocaleDef] {
+    &active().enabled
+}
+
+pub fn default_locale() -> &'static LocaleDef {
+    active().default
+}
+
+/// The enabled locale for `code`, or the site default.
+pub fn resolve_locale(code: Option<&str>) -> &'static LocaleDef {
+    code.and_then(find_locale).unwrap_or_else(default_locale)
+}
+
+/// `code` followed by its fallbacks and the site default, limited to enabled
+/// locales and without repeats.
+pub fn fallback_chain(code: &str) -> Vec<&'static str> {
+    active().fallback_chain(code)
+}
+
+/// Picks the enabled locale that best matches an `Accept-Language` header.
+/// Ranges are tried by descending `q`, header order breaking ties; an exact
+/// tag wins over a match on the language subtag alone (`zh-TW` → `zh-CN`).
+pub fn negotiate_accept_language(header: &str) -> Option<&'static LocaleDef> {
+    active().negotiate(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locales(default_locale: &str) -> ActiveLocales {
+        let locale = |code: &str, tag: &str, fallback: &[&str]| LocaleInfo {
+            code: code.to_string(),
+            tag: tag.to_string(),
+            name: code.to_string(),
+            fallback: fallback.iter().map(|code| code.to_string()).collect(),
+            ftl: String::new(),
+        };
+        ActiveLocales::from_info(&SiteInfo {
+            locales: vec![
+                locale("cn", "zh-CN", &["en"]),
+                locale("en", "en-US", &[]),
+                locale("de", "de-DE", &["en", "missing"]),
+            ],
+            default_locale: default_locale.to_string(),
+            name: "Test".to_string(),
+            url: "https://example.com".to_string(),
+            mermaid_url: None,
+            social_links: Vec::new(),
+        })
+    }
+
+    fn negotiated(locales: &ActiveLocales, header: &str) -> Option<&'static str> {
+        locales.negotiate(header).map(|locale| locale.code)
+    }
+
+    #[test]
+    fn finds_locales_by_code_or_tag() {
+        let locales = locales("cn");
+        assert_eq!(locales.find("CN").map(|locale| locale.code), Some("cn"));
+        assert_eq!(locales.find("en-us").map(|locale| locale.code), Some("en"));
+        assert_eq!(locales.find("ja"), None);
+    }
+
+    #[test]
+    fn negotiates_by_quality_then_header_order() {
+        let locales = locales("cn");
+        assert_eq!(negotiated(&locales, "en-US;q=0.5, zh-CN"), Some("cn"));
+        assert_eq!(negotiated(&locales, "de-DE, en-US"), Some("de"));
+        assert_eq!(negotiated(&locales, "fr, en;q=0.8, de;q=0.8"), Some("en"));
+    }
+
+    #[test]
+    fn falls_back_to_the_language_subtag() {
+        let locales = locales("cn");
+        assert_eq!(negotiated(&locales, "zh-TW"), Some("cn"));
+        assert_eq!(negotiated(&locales, "en-GB;q=0.9, ja"), Some("en"));
+    }
+
+    #[test]
+    fn ignores_wildcards_zero_quality_and_garbage() {
+        let locales = locales("cn");
+        assert_eq!(negotiated(&locales, "*"), None);
+        assert_eq!(negotiated(&locales, "en;q=0, ja"), None);
+        assert_eq!(negotiated(&locales, "en;q=abc"), None);
+        assert_eq!(negotiated(&locales, ""), None);
+    }
+
+    #[test]
+    fn fallback_chains_skip_unknown_codes_and_repeats() {
+        let locales = locales("cn");
+        assert_eq!(locales.fallback_chain("de"), ["de", "en", "cn"]);
+        assert_eq!(locales.fallback_chain("cn"), ["cn", "en"]);
+        assert_eq!(locales.fallback_chain("ja"), ["cn"]);
+    }
+
+    #[test]
+    fn default_locale_must_be_enabled() {
+        assert_eq!(locales("en").default.code, "en");
+        assert_eq!(locales("ja").default.code, "cn");
+    }
+}
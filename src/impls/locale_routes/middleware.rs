@@ -0,0 +1,62 @@
+use axum::extract::Request;
+use axum::http::header::{self, HeaderValue};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::impls::i18n;
+use crate::impls::locale_routes::paths::{is_localizable_path, localized_path};
+use crate::impls::session::preference::{self, PreferenceField, PreferenceSeed};
+use crate::impls::session::AppSession;
+
+/// Redirects unprefixed page URLs such as `/blog/hello` to `/{lang}/blog/hello/`.
+/// The locale comes from the session, then `Accept-Language`, then the site default.
+pub async fn redirect_to_locale(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if !matches!(*request.method(), Method::GET | Method::HEAD) || !is_localizable_path(path) {
+        return next.run(request).await;
+    }
+
+    let lang = preferred_locale(&request);
+    let mut location = localized_path(lang, &with_post_slash(path));
+    if let Some(query) = request.uri().query() {
+        location.push('?');
+        location.push_str(query);
+    }
+    let Ok(location) = HeaderValue::from_str(&location) else {
+        return next.run(request).await;
+    };
+
+    (
+        StatusCode::FOUND,
+        [
+            (header::LOCATION, location),
+            (
+                header::VARY,
+                HeaderValue::from_static("Cookie, Accept-Language"),
+            ),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("private, no-cache"),
+            ),
+        ],
+    )
+        .into_response()
+}
+
+fn preferred_locale(request: &Request) -> &'static str {
+    let stored = request
+        .extensions()
+        .get::<AppSession>()
+        .and_then(|session| preference::get(session, PreferenceField::Locale));
+    let requested = stored.or_else(|| PreferenceSeed::from(request.headers()).inferred_locale);
+    i18n::resolve_locale(requested.as_deref()).code
+}
+
+/// Posts are served as directories so their relative `img/` links resolve.
+fn with_post_slash(path: &str) -> String {
+    match path.strip_prefix("/blog/") {
+        Some(slug) if !slug.is_empty() && !slug.contains('/') => format!("{path}/"),
+        _ => path.to_string(),
+    }
+}
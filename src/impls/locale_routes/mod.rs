@@ -0,0 +1,9 @@
+#[cfg(feature = "server")]
+mod middleware;
+#[cfg(feature = "server")]
+mod paths;
+
+#[cfg(feature = "server")]
+pub use middleware::redirect_to_locale;
+#[cfg(feature = "server")]
+pub use paths::{is_localizable_path, localized_path, split_locale_prefix};
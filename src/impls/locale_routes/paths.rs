@@ -0,0 +1,25 @@
+use crate::impls::i18n;
+
+const LOCALIZABLE_PREFIXES: [&str; 3] = ["/blog/", "/tags/", "/series/"];
+
+/// Pages that have a `/{lang}/...` variant. Post images stay unprefixed.
+pub fn is_localizable_path(path: &str) -> bool {
+    LOCALIZABLE_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+        && !path.contains("/img/")
+}
+
+pub fn localized_path(lang: &str, path: &str) -> String {
+    format!("/{lang}{path}")
+}
+
+/// Splits `/{lang}/rest` into an enabled locale and `/rest`.
+pub fn split_locale_prefix(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix('/')?;
+    let (lang, rest) = rest.split_at(rest.find('/')?);
+    i18n::enabled_locales()
+        .iter()
+        .any(|locale| locale.code == lang)
+        .then_some((lang, rest))
+}
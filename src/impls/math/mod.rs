@@ -0,0 +1,7 @@
+#[cfg(feature = "server")]
+mod scanner;
+#[cfg(feature = "server")]
+mod service;
+
+#[cfg(feature = "server")]
+pub use service::{prerender_math, MathTarget};
@@ -0,0 +1,206 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Math { source: &'a str, display: bool },
+}
+
+/// Splits markdown into prose and `$...$` / `$$...$$` formulas. Fenced code
+/// blocks, inline code spans and `\$` escapes are left untouched.
+pub fn split_math(markdown: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut fence: Option<(u8, usize)> = None;
+    let mut prose_start = 0;
+    let mut offset = 0;
+
+    for line in markdown.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let marker = fence_marker(line);
+        match (fence, marker) {
+            (None, Some(open)) => {
+                scan_prose(markdown, prose_start, line_start, &mut segments);
+                fence = Some(open);
+                prose_start = line_start;
+            }
+            (Some((ch, len)), Some((close_ch, close_len)))
+                if ch == close_ch && close_len >= len && is_closing_fence(line) =>
+            {
+                push_text(&mut segments, &markdown[prose_start..offset]);
+                fence = None;
+                prose_start = offset;
+            }
+            _ => {}
+        }
+    }
+
+    if fence.is_some() {
+        push_text(&mut segments, &markdown[prose_start..]);
+    } else {
+        scan_prose(markdown, prose_start, markdown.len(), &mut segments);
+    }
+    segments
+}
+
+fn fence_marker(line: &str) -> Option<(u8, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = *trimmed.as_bytes().first()?;
+    if ch != b'`' && ch != b'~' {
+        return None;
+    }
+    let len = trimmed.bytes().take_while(|byte| *byte == ch).count();
+    (len >= 3).then_some((ch, len))
+}
+
+fn is_closing_fence(line: &str) -> bool {
+    line.trim().bytes().all(|byte| byte == b'`' || byte == b'~')
+}
+
+fn scan_prose<'a>(markdown: &'a str, start: usize, end: usize, segments: &mut Vec<Segment<'a>>) {
+    let bytes = markdown.as_bytes();
+    let mut text_start = start;
+    let mut index = start;
+
+    while index < end {
+        match bytes[index] {
+            b'\\' => {
+                index += if bytes.get(index + 1) == Some(&b'$') {
+                    2
+                } else {
+                    1
+                }
+            }
+            b'`' => {
+                let run = count_run(bytes, index, end, b'`');
+                index = find_code_span_end(bytes, index + run, end, run).unwrap_or(index + run);
+            }
+            b'$' if bytes.get(index + 1) == Some(&b'$') => {
+                match markdown[index + 2..end].find("$$") {
+                    Some(relative)
+                        if !markdown[index + 2..index + 2 + relative].trim().is_empty() =>
+                    {
+                        let close = index + 2 + relative;
+                        push_text(segments, &markdown[text_start..index]);
+                        segments.push(Segment::Math {
+                            source: markdown[index + 2..close].trim(),
+                            display: true,
+                        });
+                        index = close + 2;
+                        text_start = index;
+                    }
+                    _ => index += 2,
+                }
+            }
+            b'$' => match find_inline_close(bytes, index, end) {
+                Some(close) => {
+                    push_text(segments, &markdown[text_start..index]);
+                    segments.push(Segment::Math {
+                        source: &markdown[index + 1..close],
+                        display: false,
+                    });
+                    index = close + 1;
+                    text_start = index;
+                }
+                None => index += 1,
+            },
+            _ => index += 1,
+        }
+    }
+
+    push_text(segments, &markdown[text_start..end]);
+}
+
+fn count_run(bytes: &[u8], start: usize, end: usize, byte: u8) -> usize {
+    bytes[start..end]
+        .iter()
+        .take_while(|value| **value == byte)
+        .count()
+}
+
+fn find_code_span_end(bytes: &[u8], from: usize, end: usize, run: usize) -> Option<usize> {
+    let mut index = from;
+    while index < end {
+        if bytes[index] == b'`' {
+            let len = count_run(bytes, index, end, b'`');
+            if len == run {
+                return Some(index + len);
+            }
+            index += len;
+        } else {
+            index += 1;
+        }
+    }
+    None
+}
+
+/// Pandoc-style rules: the opener is not followed by whitespace, the closer is
+/// not preceded by whitespace nor followed by a digit, and both sit on one line.
+/// Any other `$` before a valid closer means this is not a formula.
+fn find_inline_close(bytes: &[u8], open: usize, end: usize) -> Option<usize> {
+    let first = *bytes.get(open + 1)?;
+    if first.is_ascii_whitespace() || first == b'$' {
+        return None;
+    }
+
+    let mut index = open + 1;
+    while index < end {
+        match bytes[index] {
+            b'\n' => return None,
+            b'\\' => index += 2,
+            b'$' => {
+                let before = bytes[index - 1];
+                let after = bytes.get(index + 1).copied().unwrap_or(b' ');
+                return (!before.is_ascii_whitespace() && !after.is_ascii_digit()).then_some(index);
+            }
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+fn push_text<'a>(segments: &mut Vec<Segment<'a>>, text: &'a str) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_inline_and_display_formulas() {
+        assert_eq!(
+            split_math("Let $x^2$ be\n$$\n\\frac{a}{b}\n$$\n"),
+            [
+                Segment::Text("Let "),
+                Segment::Math {
+                    source: "x^2",
+                    display: false
+                },
+                Segment::Text(" be\n"),
+                Segment::Math {
+                    source: "\\frac{a}{b}",
+                    display: true
+                },
+                Segment::Text("\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_code_escapes_and_prices_alone() {
+        for text in [
+            "`$x$` in code",
+            "```\n$x$\n```\n",
+            "escaped \\$x\\$ dollars",
+            "costs $5 and $10",
+            "a $ b $ c",
+        ] {
+            assert_eq!(split_math(text), [Segment::Text(text)], "{text}");
+        }
+    }
+}
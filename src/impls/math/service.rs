@@ -0,0 +1,124 @@
+use latex2mathml::{latex_to_mathml, DisplayStyle};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::impls::math::scanner::{split_math, Segment};
+use crate::impls::site::component_tag;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathTarget {
+    /// `<Math mathml="..."/>` tags picked up by the markdown renderer's custom components.
+    Component,
+    /// Bare `<math>` elements for consumers that emit HTML directly (feeds).
+    Html,
+}
+
+static MATHML_CACHE: OnceLock<RwLock<HashMap<(String, bool), Option<String>>>> = OnceLock::new();
+
+/// Replaces LaTeX formulas in markdown with pre-rendered MathML. Formulas that
+/// fail to convert are kept as written.
+pub fn prerender_math(markdown: &str, target: MathTarget) -> String {
+    let segments = split_math(markdown);
+    if !segments
+        .iter()
+        .any(|segment| matches!(segment, Segment::Math { .. }))
+    {
+        return markdown.to_string();
+    }
+
+    let mut output = String::with_capacity(markdown.len() * 2);
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Math { source, display } => match render_formula(source, display) {
+                Some(mathml) => push_math(&mut output, &mathml, display, target),
+                None if display => {
+                    output.push_str("$$");
+                    output.push_str(source);
+                    output.push_str("$$");
+                }
+                None => {
+                    output.push('$');
+                    output.push_str(source);
+                    output.push('$');
+                }
+            },
+        }
+    }
+    output
+}
+
+fn render_formula(source: &str, display: bool) -> Option<String> {
+    let key = (source.to_string(), display);
+    let cache = MATHML_CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+
+    if let Ok(state) = cache.read() {
+        if let Some(cached) = state.get(&key) {
+            return cached.clone();
+        }
+    }
+
+    let style = if display {
+        DisplayStyle::Block
+    } else {
+        DisplayStyle::Inline
+    };
+    let rendered = match latex_to_mathml(source, style) {
+        Ok(mathml) => Some(mathml.replace(['\r', '\n'], "")),
+        Err(error) => {
+            log::warn!("failed to render formula `{source}`: {error}");
+            None
+        }
+    };
+
+    if let Ok(mut state) = cache.write() {
+        state.insert(key, rendered.clone());
+    }
+    rendered
+}
+
+fn push_math(output: &mut String, mathml: &str, display: bool, target: MathTarget) {
+    match target {
+        MathTarget::Html => output.push_str(mathml),
+        MathTarget::Component if display => output.push_str(&component_tag(
+            "Math",
+            &[("display", "block"), ("mathml", mathml)],
+        )),
+        MathTarget::Component => output.push_str(&component_tag("Math", &[("mathml", mathml)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_inline_formulas_as_math_components() {
+        let rendered = prerender_math("Energy $E = mc^2$ holds.", MathTarget::Component);
+        assert!(
+            rendered.starts_with("Energy <Math mathml=\"%3Cmath"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("%3Cmi%3EE%3C/mi%3E"), "{rendered}");
+        assert!(rendered.ends_with("\"/> holds."), "{rendered}");
+    }
+
+    #[test]
+    fn renders_display_formulas_as_block_math() {
+        let markdown = "Before\n\n$$\n\\frac{a}{b}\n$$\n\nAfter\n";
+        let component = prerender_math(markdown, MathTarget::Component);
+        assert!(component.contains("<Math display=\"block\" mathml=\"%3Cmath"));
+        assert!(component.contains("mfrac"));
+        assert!(component.starts_with("Before\n\n") && component.ends_with("\n\nAfter\n"));
+
+        let html = prerender_math(markdown, MathTarget::Html);
+        assert!(html.contains("<math") && html.contains("<mfrac>"), "{html}");
+        assert!(!html.contains("$$"));
+    }
+
+    #[test]
+    fn keeps_markdown_without_formulas_unchanged() {
+        let markdown = "Prices are $5 and $10, `$x$` is code.\n";
+        assert_eq!(prerender_math(markdown, MathTarget::Component), markdown);
+    }
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "server")]
+mod scanner;
+#[cfg(feature = "server")]
+mod service;
+
+#[cfg(feature = "server")]
+pub use scanner::contains_diagram;
+#[cfg(feature = "server")]
+pub use service::{preformat_diagrams, prerender_diagrams, render_diagrams};
@@ -0,0 +1,87 @@
+use std::ops::Range;
+
+const OPEN_TAG: &str = "<Mermaid";
+const CLOSE_TAG: &str = "</Mermaid>";
+
+/// A `<Mermaid>...</Mermaid>` block starting at the beginning of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagramBlock<'a> {
+    /// Byte range of the whole block, tags included.
+    pub range: Range<usize>,
+    pub definition: &'a str,
+}
+
+/// Whether the markdown uses the Mermaid component at all, in either its
+/// block or its self-closing `content="..."` form.
+pub fn contains_diagram(markdown: &str) -> bool {
+    prose_lines(markdown).any(|(_, line)| open_tag_end(line).is_some())
+}
+
+/// Diagram blocks outside fenced code, in document order.
+pub fn find_diagrams(markdown: &str) -> Vec<DiagramBlock<'_>> {
+    let mut blocks = Vec::new();
+    let mut resume_at = 0;
+
+    for (line_start, line) in prose_lines(markdown) {
+        if line_start < resume_at {
+            continue;
+        }
+        let Some(tag_end) = open_tag_end(line) else {
+            continue;
+        };
+        if line[..tag_end].ends_with("/>") {
+            continue;
+        }
+
+        let body_start = line_start + tag_end;
+        let Some(close) = markdown[body_start..].find(CLOSE_TAG) else {
+            break;
+        };
+        let body_end = body_start + close;
+        let block_end = body_end + CLOSE_TAG.len();
+        blocks.push(DiagramBlock {
+            range: line_start..block_end,
+            definition: markdown[body_start..body_end].trim(),
+        });
+        resume_at = block_end;
+    }
+
+    blocks
+}
+
+/// Byte offset just past the `>` of an opening tag at the start of `line`.
+fn open_tag_end(line: &str) -> Option<usize> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = line[indent..].strip_prefix(OPEN_TAG)?;
+    if !rest.starts_with(['>', ' ', '\t', '/']) {
+        return None;
+    }
+    rest.find('>').map(|end| indent + OPEN_TAG.len() + end + 1)
+}
+
+/// Lines with their byte offsets, skipping fenced code blocks.
+fn prose_lines(markdown: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+    markdown.split_inclusive('\n').filter_map(move |line| {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                None
+            }
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                None
+            }
+            (Some(_), _) => None,
+            (None, None) => Some((line_start, line)),
+        }
+    })
+}
@@ -0,0 +1,215 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{OnceLock, RwLock};
+
+use crate::impls::mermaid::scanner::find_diagrams;
+use crate::impls::site::{component_tag, escape_xml};
+use crate::impls::theme;
+
+const CACHE_DIR_ENV: &str = "MERMAID_CACHE_DIR";
+const DEFAULT_CACHE_DIR: &str = ".cache/mermaid";
+const CLI_ENV: &str = "MERMAID_CLI";
+const DEFAULT_CLI: &str = "mmdc";
+/// Optional Puppeteer config for `mmdc`, e.g. to pass `--no-sandbox` in
+/// containers.
+const PUPPETEER_CONFIG_ENV: &str = "MERMAID_PUPPETEER_CONFIG";
+
+static SVG_CACHE: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+
+#[derive(Debug, Default)]
+pub struct DiagramReport {
+    pub rendered: usize,
+    pub cached: usize,
+    pub failed: Vec<String>,
+}
+
+/// The Mermaid themes the theme registry uses, each rendered once.
+fn prerender_themes() -> Vec<&'static str> {
+    let mut themes = Vec::new();
+    for def in theme::themes() {
+        if !themes.contains(&def.mermaid_theme) {
+            themes.push(def.mermaid_theme);
+        }
+    }
+    themes
+}
+
+/// Swaps `<Mermaid>` blocks that have a pre-rendered SVG for every theme in
+/// the cache for `<Mermaid source="..." svgs="..."/>` tags, `svgs` being a
+/// JSON map from Mermaid theme to SVG. Other blocks are left for the browser
+/// to render.
+pub fn prerender_diagrams(markdown: &str) -> String {
+    let blocks = find_diagrams(markdown);
+    if blocks.is_empty() {
+        return markdown.to_string();
+    }
+
+    let themes = prerender_themes();
+    let mut output = String::with_capacity(markdown.len());
+    let mut cursor = 0;
+    for block in blocks {
+        let Some(svgs) = themes
+            .iter()
+            .map(|theme| {
+                cached_svg(&diagram_hash(theme, block.definition)).map(|svg| (*theme, svg))
+            })
+            .collect::<Option<HashMap<_, _>>>()
+        else {
+            continue;
+        };
+        let Ok(svgs) = serde_json::to_string(&svgs) else {
+            continue;
+        };
+        output.push_str(&markdown[cursor..block.range.start]);
+        output.push_str(&component_tag(
+            "Mermaid",
+            &[("source", block.definition), ("svgs", &svgs)],
+        ));
+        cursor = block.range.end;
+    }
+    output.push_str(&markdown[cursor..]);
+    output
+}
+
+/// Swaps the `<Mermaid>` blocks [`prerender_diagrams`] left alone for a
+/// `<pre class="mermaid">` of their definition, for output that never runs
+/// scripts such as feeds.
+pub fn preformat_diagrams(markdown: &str) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut cursor = 0;
+    for block in find_diagrams(markdown) {
+        output.push_str(&markdown[cursor..block.range.start]);
+        output.push_str(&format!(
+            r#"<pre class="mermaid">{}</pre>"#,
+            escape_xml(block.definition)
+        ));
+        cursor = block.range.end;
+    }
+    output.push_str(&markdown[cursor..]);
+    output
+}
+
+/// Renders every diagram in `markdowns` that is not cached yet, once per
+/// Mermaid theme in the theme registry, with the Mermaid CLI (`MERMAID_CLI`,
+/// default `mmdc`). Meant to run at build time.
+pub fn render_diagrams<'a>(markdowns: impl IntoIterator<Item = &'a str>) -> DiagramReport {
+    let mut report = DiagramReport::default();
+    let cache_dir = cache_dir();
+    if let Err(error) = fs::create_dir_all(&cache_dir) {
+        report
+            .failed
+            .push(format!("failed to create {}: {error}", cache_dir.display()));
+        return report;
+    }
+
+    let themes = prerender_themes();
+    let mut seen = Vec::new();
+    for markdown in markdowns {
+        for block in find_diagrams(markdown) {
+            for theme in themes.iter().copied() {
+                let hash = diagram_hash(theme, block.definition);
+                if seen.contains(&hash) {
+                    continue;
+                }
+                seen.push(hash.clone());
+
+                let target = svg_path(&cache_dir, &hash);
+                if target.is_file() {
+                    report.cached += 1;
+                    continue;
+                }
+                match run_cli(&cache_dir, &hash, theme, block.definition, &target) {
+                    Ok(()) => report.rendered += 1,
+                    Err(error) => report
+                        .failed
+                        .push(format!("diagram {hash} ({theme}): {error}")),
+                }
+            }
+        }
+    }
+    report
+}
+
+fn run_cli(
+    cache_dir: &Path,
+    hash: &str,
+    theme: &str,
+    definition: &str,
+    target: &Path,
+) -> Result<(), String> {
+    let input = cache_dir.join(format!("{hash}.mmd"));
+    let partial = cache_dir.join(format!("{hash}.partial.svg"));
+    let config = cache_dir.join(format!("{hash}.config.json"));
+    fs::write(&input, definition).map_err(|error| error.to_string())?;
+    fs::write(&config, format!(r#"{{"theme":"{theme}"}}"#)).map_err(|error| error.to_string())?;
+
+    let cli = std::env::var(CLI_ENV).unwrap_or_else(|_| DEFAULT_CLI.to_string());
+    let mut command = Command::new(&cli);
+    command
+        .arg("--input")
+        .arg(&input)
+        .arg("--output")
+        .arg(&partial)
+        .arg("--configFile")
+        .arg(&config)
+        .args(["--backgroundColor", "transparent", "--quiet"]);
+    if let Some(puppeteer_config) = std::env::var_os(PUPPETEER_CONFIG_ENV) {
+        command.arg("--puppeteerConfigFile").arg(puppeteer_config);
+    }
+    let output = command
+        .output()
+        .map_err(|error| format!("failed to run {cli}: {error}"));
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&config);
+
+    let output = output?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&partial);
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    fs::rename(&partial, target).map_err(|error| error.to_string())
+}
+
+fn cached_svg(hash: &str) -> Option<String> {
+    let cache = SVG_CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+    if let Ok(state) = cache.read() {
+        if let Some(svg) = state.get(hash) {
+            return Some(svg.clone());
+        }
+    }
+
+    // Misses are not remembered: the build step may fill the cache later.
+    let svg = fs::read_to_string(svg_path(&cache_dir(), hash)).ok()?;
+    if let Ok(mut state) = cache.write() {
+        state.insert(hash.to_string(), svg.clone());
+    }
+    Some(svg)
+}
+
+/// Cache key of a diagram rendered in a Mermaid theme.
+fn diagram_hash(theme: &str, definition: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(theme)
+        .chain_update([0u8])
+        .chain_update(definition.trim())
+        .finalize();
+    digest[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn svg_path(cache_dir: &Path, hash: &str) -> PathBuf {
+    cache_dir.join(format!("{hash}.svg"))
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::var(CACHE_DIR_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR))
+}
@@ -1,4 +1,13 @@
 pub mod about;
 pub mod blog;
+pub mod feed;
+pub mod http_cache;
 pub mod i18n;
+pub mod locale_routes;
+pub mod math;
+pub mod mermaid;
+pub mod search;
 pub mod session;
+pub mod site;
+pub mod sitemap;
+pub mod theme;
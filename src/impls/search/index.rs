@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::impls::blog::PostMetadata;
+use crate::impls::search::tokenizer::tokenize;
+use crate::impls::search::types::{SearchHit, SnippetSegment};
+
+pub const SEARCH_RESULT_LIMIT: usize = 20;
+/// Longest query searched; anything past it is ignored.
+pub const SEARCH_QUERY_MAX_CHARS: usize = 200;
+
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+const TITLE_WEIGHT: u32 = 3;
+const SNIPPET_CHARS_BEFORE: usize = 40;
+const SNIPPET_CHARS_AFTER: usize = 120;
+/// Characters of each post shipped in the client-side index. The server keeps
+/// whole bodies so matches past this point still get a highlighted snippet.
+const SNIPPET_SOURCE_CHARS: usize = 1_500;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    docs: Vec<IndexedDocument>,
+    postings: HashMap<String, Vec<Posting>>,
+    avg_len: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexedDocument {
+    meta: PostMetadata,
+    text: String,
+    len: u32,
+}
+
+// Serialized as `[doc, tf]` pairs to keep the shipped index small.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Posting(u32, u32);
+
+impl SearchIndex {
+    pub fn asset_path(lang: &str) -> String {
+        format!("/search-index/{lang}.json")
+    }
+
+    #[cfg(feature = "server")]
+    pub fn build(documents: impl IntoIterator<Item = (PostMetadata, String)>) -> Self {
+        let mut docs = Vec::new();
+        let mut postings = HashMap::<String, Vec<Posting>>::new();
+        let mut total_len = 0u64;
+
+        for (doc_id, (meta, text)) in documents.into_iter().enumerate() {
+            let mut frequencies = HashMap::<String, u32>::new();
+            for token in tokenize(&meta.title) {
+                *frequencies.entry(token.term).or_insert(0) += TITLE_WEIGHT;
+            }
+
+            let mut len = 0u32;
+            for token in tokenize(&text) {
+                *frequencies.entry(token.term).or_insert(0) += 1;
+                len += 1;
+            }
+
+            for (term, tf) in frequencies {
+                postings
+                    .entry(term)
+                    .or_default()
+                    .push(Posting(doc_id as u32, tf));
+            }
+            total_len += u64::from(len);
+            docs.push(IndexedDocument { meta, text, len });
+        }
+
+        let avg_len = if docs.is_empty() {
+            0.0
+        } else {
+            total_len as f32 / docs.len() as f32
+        };
+
+        Self {
+            docs,
+            postings,
+            avg_len,
+        }
+    }
+
+    /// The index shipped to the browser: the same postings with each body cut
+    /// to [`SNIPPET_SOURCE_CHARS`] to keep it small.
+    #[cfg(feature = "server")]
+    pub fn for_client(&self) -> Self {
+        let docs = self
+            .docs
+            .iter()
+            .map(|doc| {
+                let end = offset_chars_after(&doc.text, 0, SNIPPET_SOURCE_CHARS);
+                IndexedDocument {
+                    meta: doc.meta.clone(),
+                    text: doc.text[..end].to_string(),
+                    len: doc.len,
+                }
+            })
+            .collect();
+        Self {
+            docs,
+            postings: self.postings.clone(),
+            avg_len: self.avg_len,
+        }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_terms = query_terms(query);
+        if query_terms.is_empty() || self.docs.is_empty() {
+            return Vec::new();
+        }
+
+        let matched_terms = query_terms
+            .iter()
+            .flat_map(|term| self.expand_term(term))
+            .collect::<Vec<_>>();
+
+        let doc_count = self.docs.len() as f32;
+        let avg_len = self.avg_len.max(1.0);
+        let mut scores = HashMap::<u32, f32>::new();
+        for term in matched_terms.iter() {
+            let Some(postings) = self.postings.get(term.as_str()) else {
+                continue;
+            };
+            let df = postings.len() as f32;
+            let idf = ((doc_count - df + 0.5) / (df + 0.5) + 1.0).ln();
+            for &Posting(doc_id, tf) in postings {
+                let doc_len = self.docs[doc_id as usize].len as f32;
+                let tf = tf as f32;
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avg_len);
+                *scores.entry(doc_id).or_insert(0.0) += idf * tf * (BM25_K1 + 1.0) / (tf + norm);
+            }
+        }
+
+        let mut ranked = scores.into_iter().collect::<Vec<_>>();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(doc_id, score)| {
+                let doc = &self.docs[doc_id as usize];
+                SearchHit {
+                    meta: doc.meta.clone(),
+                    score,
+                    snippet: build_snippet(&doc.text, &query_terms),
+                }
+            })
+            .collect()
+    }
+
+    fn expand_term(&self, term: &str) -> Vec<String> {
+        if self.postings.contains_key(term) {
+            return vec![term.to_string()];
+        }
+        self.postings
+            .keys()
+            .filter(|candidate| candidate.starts_with(term))
+            .cloned()
+            .collect()
+    }
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    let query = match query.char_indices().nth(SEARCH_QUERY_MAX_CHARS) {
+        Some((end, _)) => &query[..end],
+        None => query,
+    };
+    let mut terms = Vec::new();
+    for token in tokenize(query) {
+        if !terms.contains(&token.term) {
+            terms.push(token.term);
+        }
+    }
+    terms
+}
+
+fn build_snippet(text: &str, query_terms: &[String]) -> Vec<SnippetSegment> {
+    let mut ranges = tokenize(text)
+        .into_iter()
+        .filter(|token| query_terms.iter().any(|term| token.term.starts_with(term)))
+        .map(|token| (token.start, token.end))
+        .collect::<Vec<_>>();
+    ranges.sort();
+
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let anchor = merged.first().map(|range| range.0).unwrap_or(0);
+    let window_start = offset_chars_before(text, anchor, SNIPPET_CHARS_BEFORE);
+    let window_end = offset_chars_after(text, anchor, SNIPPET_CHARS_AFTER);
+
+    let mut segments = Vec::new();
+    let mut cursor = window_start;
+    for (start, end) in merged {
+        if end <= window_start || start >= window_end {
+            continue;
+        }
+        let start = start.max(window_start);
+        let end = end.min(window_end);
+        if cursor < start {
+            push_segment(&mut segments, &text[cursor..start], false);
+        }
+        push_segment(&mut segments, &text[start..end], true);
+        cursor = end;
+    }
+    if cursor < window_end {
+        push_segment(&mut segments, &text[cursor..window_end], false);
+    }
+
+    if window_start > 0 {
+        segments.insert(
+            0,
+            SnippetSegment {
+                text: "…".to_string(),
+                highlighted: false,
+            },
+        );
+    }
+    if window_end < text.len() {
+        push_segment(&mut segments, "…", false);
+    }
+    segments
+}
+
+fn push_segment(segments: &mut Vec<SnippetSegment>, text: &str, highlighted: bool) {
+    if text.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some(last) if last.highlighted == highlighted => last.text.push_str(text),
+        _ => segments.push(SnippetSegment {
+            text: text.to_string(),
+            highlighted,
+        }),
+    }
+}
+
+fn offset_chars_before(text: &str, offset: usize, count: usize) -> usize {
+    if count == 0 {
+        return offset;
+    }
+    text[..offset]
+        .char_indices()
+        .rev()
+        .nth(count - 1)
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+fn offset_chars_after(text: &str, offset: usize, count: usize) -> usize {
+    text[offset..]
+        .char_indices()
+        .nth(count)
+        .map(|(index, _)| offset + index)
+        .unwrap_or(text.len())
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+
+    fn meta(slug: &str) -> PostMetadata {
+        PostMetadata {
+            title: slug.to_string(),
+            date: "2024-01-01".to_string(),
+            slug: slug.to_string(),
+            lang: "en".to_string(),
+            tags: None,
+            series: None,
+            summary: None,
+            draft: false,
+            publish_at: None,
+            untranslated: false,
+            outdated_from: None,
+            has_mermaid: false,
+            word_count: 0,
+        }
+    }
+
+    #[test]
+    fn ships_only_a_snippet_sized_prefix_of_each_body() {
+        let body = format!("{} needle", "filler ".repeat(1_000));
+        let index = SearchIndex::build([(meta("long"), body)]);
+
+        let hits = index.search("needle", SEARCH_RESULT_LIMIT);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0]
+            .snippet
+            .iter()
+            .any(|segment| segment.highlighted && segment.text == "needle"));
+
+        let client = index.for_client();
+        assert_eq!(client.docs[0].text.chars().count(), SNIPPET_SOURCE_CHARS);
+        assert_eq!(client.search("needle", SEARCH_RESULT_LIMIT).len(), 1);
+    }
+
+    #[test]
+    fn ranks_title_matches_first() {
+        let index = SearchIndex::build([
+            (meta("other"), "a note about matrices".to_string()),
+            (meta("matrices"), "unrelated text".to_string()),
+        ]);
+        let hits = index.search("matrices", SEARCH_RESULT_LIMIT);
+        assert_eq!(hits[0].meta.slug, "matrices");
+        assert_eq!(hits.len(), 2);
+    }
+
+    #[test]
+    fn ignores_query_text_past_the_limit() {
+        let index = SearchIndex::build([(meta("post"), "needle".to_string())]);
+        let padding = "x".repeat(SEARCH_QUERY_MAX_CHARS);
+        assert!(index.search(&format!("{padding} needle"), 5).is_empty());
+        assert_eq!(index.search(&format!("needle {padding}"), 5).len(), 1);
+    }
+}
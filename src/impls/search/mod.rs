@@ -0,0 +1,15 @@
+mod index;
+#[cfg(feature = "server")]
+mod routes;
+#[cfg(feature = "server")]
+mod service;
+mod tokenizer;
+mod types;
+
+pub use index::{SearchIndex, SEARCH_QUERY_MAX_CHARS, SEARCH_RESULT_LIMIT};
+#[cfg(feature = "server")]
+pub use routes::router;
+#[cfg(feature = "server")]
+pub use service::{get_search_index_documents, markdown_to_plain_text, search_posts};
+pub use tokenizer::tokenize;
+pub use types::SearchHit;
@@ -0,0 +1,32 @@
+use axum::extract::Path;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+
+use crate::impls::blog;
+use crate::impls::search::service::search_index_json;
+
+pub fn router() -> Router {
+    Router::new().route("/search-index/{file}", get(search_index))
+}
+
+async fn search_index(Path(file): Path<String>) -> Response {
+    let Some(lang) = file.strip_suffix(".json") else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if !blog::get_available_languages()
+        .iter()
+        .any(|value| value == lang)
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    match search_index_json(lang) {
+        Ok(json) => ([(header::CONTENT_TYPE, "application/json")], json).into_response(),
+        Err(error) => {
+            log::warn!("failed to serialize search index for {lang}: {error}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::impls::blog;
+use crate::impls::search::index::{SearchIndex, SEARCH_RESULT_LIMIT};
+use crate::impls::search::types::SearchHit;
+
+struct CachedIndex {
+    signature: u64,
+    index: Arc<SearchIndex>,
+}
+
+static INDEX_CACHE: OnceLock<RwLock<HashMap<String, CachedIndex>>> = OnceLock::new();
+
+pub fn search_posts(query: &str, lang: &str) -> Vec<SearchHit> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    get_search_index(lang).search(query, SEARCH_RESULT_LIMIT)
+}
+
+pub fn get_search_index(lang: &str) -> Arc<SearchIndex> {
+    let signature = blog::content_version(lang);
+    let cache = INDEX_CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+
+    if let Ok(state) = cache.read() {
+        if let Some(cached) = state.get(lang) {
+            if cached.signature == signature {
+                return cached.index.clone();
+            }
+        }
+    }
+
+    let posts = blog::get_posts_with_content_by_lang(lang);
+    let index = Arc::new(SearchIndex::build(posts.into_iter().map(|post| {
+        let text = markdown_to_plain_text(&post.content);
+        (post.meta.clone(), text)
+    })));
+
+    if let Ok(mut state) = cache.write() {
+        state.insert(
+            lang.to_string(),
+            CachedIndex {
+                signature,
+                index: index.clone(),
+            },
+        );
+    }
+
+    index
+}
+
+/// The index for `lang` as shipped to the browser.
+pub fn search_index_json(lang: &str) -> serde_json::Result<String> {
+    serde_json::to_string(&get_search_index(lang).for_client())
+}
+
+/// Each language's search index as `(path, json)`.
+pub fn get_search_index_documents() -> Vec<(String, String)> {
+    blog::get_available_languages()
+        .iter()
+        .filter_map(|lang| {
+            let json = search_index_json(lang).ok()?;
+            Some((SearchIndex::asset_path(lang), json))
+        })
+        .collect()
+}
+
+pub fn markdown_to_plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(value) | Event::Code(value) => text.push_str(&value),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::Start(Tag::Item) | Event::End(TagEnd::Paragraph | TagEnd::Heading(_)) => {
+                text.push(' ')
+            }
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
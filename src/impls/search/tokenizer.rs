@@ -0,0 +1,74 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub term: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Splits text into search terms: latin/numeric runs become lowercase words,
+/// CJK runs become overlapping character bigrams (a lone CJK char stays a unigram).
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word_start: Option<usize> = None;
+    let mut cjk_run: Vec<(usize, char)> = Vec::new();
+
+    for (index, ch) in text.char_indices() {
+        if is_cjk(ch) {
+            flush_word(text, &mut word_start, index, &mut tokens);
+            cjk_run.push((index, ch));
+        } else if ch.is_alphanumeric() {
+            flush_cjk(&mut cjk_run, &mut tokens);
+            word_start.get_or_insert(index);
+        } else {
+            flush_word(text, &mut word_start, index, &mut tokens);
+            flush_cjk(&mut cjk_run, &mut tokens);
+        }
+    }
+
+    flush_word(text, &mut word_start, text.len(), &mut tokens);
+    flush_cjk(&mut cjk_run, &mut tokens);
+    tokens
+}
+
+pub fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x3040..=0x30FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0x20000..=0x2A6DF
+    )
+}
+
+fn flush_word(text: &str, word_start: &mut Option<usize>, end: usize, tokens: &mut Vec<Token>) {
+    if let Some(start) = word_start.take() {
+        tokens.push(Token {
+            term: text[start..end].to_lowercase(),
+            start,
+            end,
+        });
+    }
+}
+
+fn flush_cjk(run: &mut Vec<(usize, char)>, tokens: &mut Vec<Token>) {
+    if let [(start, ch)] = run.as_slice() {
+        tokens.push(Token {
+            term: ch.to_string(),
+            start: *start,
+            end: start + ch.len_utf8(),
+        });
+    } else {
+        for pair in run.windows(2) {
+            let (start, first) = pair[0];
+            let (second_start, second) = pair[1];
+            tokens.push(Token {
+                term: format!("{first}{second}"),
+                start,
+                end: second_start + second.len_utf8(),
+            });
+        }
+    }
+    run.clear();
+}
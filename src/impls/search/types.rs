@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use crate::impls::blog::PostMetadata;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub meta: PostMetadata,
+    pub score: f32,
+    pub snippet: Vec<SnippetSegment>,
+}
@@ -1,3 +1,9 @@
 pub const SESSION_COOKIE_NAME: &str = "zhiyanzhaijie_session";
 pub const SESSION_PREFERENCE_LOCALE_KEY: &str = "session.preference.locale";
 pub const SESSION_PREFERENCE_THEME_KEY: &str = "session.preference.theme";
+pub const SESSION_PREFERENCE_FALLBACK_NOTICE_KEY: &str = "session.preference.fallback_notice";
+/// Subdirectory (for the file store) that holds the sessions.
+pub const SESSION_TABLE_NAME: &str = "sessions";
+/// Cookie the bootstrap script writes with the resolved `prefers-color-scheme`,
+/// so SSR can pick the matching code highlighting for the "system" theme.
+pub const SYSTEM_THEME_COOKIE_NAME: &str = "prefers_color_scheme";
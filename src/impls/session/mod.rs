@@ -1,5 +1,11 @@
 #[cfg(feature = "server")]
-pub type AppSession = axum_session::Session<axum_session::SessionNullPool>;
+mod store;
+
+#[cfg(feature = "server")]
+pub use store::init_session_store;
+
+#[cfg(feature = "server")]
+pub type AppSession = axum_session::Session<store::SessionPool>;
 
 #[cfg(not(feature = "server"))]
 pub type AppSession = ();
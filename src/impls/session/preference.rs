@@ -4,40 +4,62 @@ mod server {
     use axum::http::{request::Parts, HeaderMap};
     use dioxus::prelude::ServerFnError;
 
+    use crate::impls::i18n;
     use crate::impls::session::consts::{
-        SESSION_PREFERENCE_LOCALE_KEY, SESSION_PREFERENCE_THEME_KEY,
+        SESSION_PREFERENCE_FALLBACK_NOTICE_KEY, SESSION_PREFERENCE_LOCALE_KEY,
+        SESSION_PREFERENCE_THEME_KEY, SYSTEM_THEME_COOKIE_NAME,
     };
     use crate::impls::session::AppSession;
+    use crate::impls::theme as app_theme;
+    use crate::utils::cookie::read_cookie;
 
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum PreferenceField {
         Locale,
         Theme,
+        /// Set once the reader dismisses the "shown in another language"
+        /// notice on a fallback post.
+        FallbackNotice,
     }
 
     impl PreferenceField {
+        pub const ALL: [Self; 3] = [Self::Locale, Self::Theme, Self::FallbackNotice];
+
         fn key(self) -> &'static str {
             match self {
                 Self::Locale => SESSION_PREFERENCE_LOCALE_KEY,
                 Self::Theme => SESSION_PREFERENCE_THEME_KEY,
+                Self::FallbackNotice => SESSION_PREFERENCE_FALLBACK_NOTICE_KEY,
             }
         }
     }
 
+    const FALLBACK_NOTICE_DISMISSED: &str = "dismissed";
+
     #[derive(Clone, Debug, Default)]
     pub struct PreferenceSeed {
         pub accept_language: Option<String>,
         pub inferred_locale: Option<String>,
+        /// Color scheme the browser reported last time, used to render the
+        /// "system" theme before the client can ask `matchMedia`.
+        pub system_theme: Option<String>,
     }
 
     impl From<&HeaderMap> for PreferenceSeed {
         fn from(headers: &HeaderMap) -> Self {
             let accept_language = read_header(headers, "accept-language");
-            let inferred_locale = infer_locale(accept_language.as_deref());
+            let inferred_locale = accept_language
+                .as_deref()
+                .and_then(i18n::negotiate_accept_language)
+                .map(|locale| locale.code.to_string());
+            let system_theme = read_cookie(headers, SYSTEM_THEME_COOKIE_NAME)
+                .filter(|theme| matches!(*theme, "light" | "dark"))
+                .map(str::to_string);
 
             Self {
                 accept_language,
                 inferred_locale,
+                system_theme,
             }
         }
     }
@@ -87,34 +109,36 @@ mod server {
         get(session, PreferenceField::Theme)
     }
 
+    pub fn system_theme(seed: &PreferenceSeed) -> Option<String> {
+        seed.system_theme.clone()
+    }
+
+    pub fn fallback_notice_dismissed(session: &AppSession) -> bool {
+        get(session, PreferenceField::FallbackNotice).as_deref() == Some(FALLBACK_NOTICE_DISMISSED)
+    }
+
+    pub fn dismiss_fallback_notice(session: &AppSession) {
+        set(
+            session,
+            PreferenceField::FallbackNotice,
+            FALLBACK_NOTICE_DISMISSED,
+        );
+    }
+
     pub fn update_locale(session: &AppSession, locale: &str) {
-        let locale = match locale {
-            "en" => "en",
-            _ => "cn",
-        };
-        set(session, PreferenceField::Locale, locale);
+        set(
+            session,
+            PreferenceField::Locale,
+            i18n::resolve_locale(Some(locale)).code,
+        );
     }
 
     pub fn update_theme(session: &AppSession, theme: &str) {
-        let theme = match theme {
-            "dark" => "dark",
-            _ => "light",
-        };
-        set(session, PreferenceField::Theme, theme);
-    }
-
-    fn infer_locale(raw: Option<&str>) -> Option<String> {
-        let raw = raw?;
-        raw.split(',').find_map(|part| {
-            let locale = part.split(';').next()?.trim().to_ascii_lowercase();
-            if locale.starts_with("en") {
-                return Some("en".to_string());
-            }
-            if locale.starts_with("zh") || locale.starts_with("cn") {
-                return Some("cn".to_string());
-            }
-            None
-        })
+        set(
+            session,
+            PreferenceField::Theme,
+            app_theme::resolve_theme_preference(Some(theme)),
+        );
     }
 
     fn read_header(headers: &HeaderMap, key: &'static str) -> Option<String> {
@@ -138,6 +162,7 @@ mod client {
     pub enum PreferenceField {
         Locale,
         Theme,
+        FallbackNotice,
     }
 
     #[derive(Clone, Debug, Default)]
@@ -159,6 +184,16 @@ mod client {
         None
     }
 
+    pub fn system_theme(_seed: &PreferenceSeed) -> Option<String> {
+        None
+    }
+
+    pub fn fallback_notice_dismissed(_session: &AppSession) -> bool {
+        false
+    }
+
+    pub fn dismiss_fallback_notice(_session: &AppSession) {}
+
     pub fn update_locale(_session: &AppSession, _locale: &str) {}
 
     pub fn update_theme(_session: &AppSession, _theme: &str) {}
@@ -0,0 +1,322 @@
+use async_trait::async_trait;
+use axum_session::{
+    DatabaseError, DatabasePool, SameSite, SessionConfig, SessionMode, SessionStore,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::fs;
+
+use crate::impls::session::consts::{SESSION_COOKIE_NAME, SESSION_TABLE_NAME};
+use crate::impls::site::{SessionSettings, SessionStoreKind, SiteConfig};
+
+/// Backend for `AppSession`, picked by `[session] store` in `site.toml`.
+#[derive(Clone, Debug)]
+pub enum SessionPool {
+    /// Keeps nothing beyond axum_session's in-memory cache.
+    Memory,
+    File(FileSessionPool),
+}
+
+/// Stores each session as `{dir}/{table}/{id}.json`, written atomically so a
+/// crash mid-write never leaves a truncated session behind.
+#[derive(Clone, Debug)]
+pub struct FileSessionPool {
+    dir: Arc<PathBuf>,
+}
+
+/// Keeps the temporary files of concurrent writes to one session apart.
+static WRITE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    /// Unix timestamp (seconds) after which the session is discarded.
+    expires: i64,
+    session: String,
+}
+
+impl FileSessionPool {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Arc::new(dir.into()),
+        }
+    }
+
+    fn table_dir(&self, table_name: &str) -> PathBuf {
+        self.dir.join(table_name)
+    }
+
+    fn session_path(&self, id: &str, table_name: &str) -> Result<PathBuf, DatabaseError> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+        if !valid {
+            return Err(DatabaseError::GenericSelectError(format!(
+                "invalid session id '{id}'"
+            )));
+        }
+        Ok(self.table_dir(table_name).join(format!("{id}.json")))
+    }
+
+    async fn read(path: &Path) -> Option<StoredSession> {
+        let text = fs::read_to_string(path).await.ok()?;
+        match serde_json::from_str(&text) {
+            Ok(stored) => Some(stored),
+            Err(error) => {
+                log::warn!("discarding unreadable session {}: {error}", path.display());
+                let _ = fs::remove_file(path).await;
+                None
+            }
+        }
+    }
+
+    async fn session_files(&self, table_name: &str) -> Result<Vec<PathBuf>, DatabaseError> {
+        let mut entries = match fs::read_dir(self.table_dir(table_name)).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(DatabaseError::GenericSelectError(error.to_string())),
+        };
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|error| DatabaseError::GenericSelectError(error.to_string()))?
+        {
+            let path = entry.path();
+            if path.extension().and_then(|value| value.to_str()) == Some("json") {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+}
+
+fn session_id(path: &Path) -> Option<String> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string)
+}
+
+fn is_expired(stored: &StoredSession) -> bool {
+    stored.expires <= Utc::now().timestamp()
+}
+
+#[async_trait]
+impl DatabasePool for SessionPool {
+    async fn initiate(&self, table_name: &str) -> Result<(), DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(());
+        };
+        fs::create_dir_all(pool.table_dir(table_name))
+            .await
+            .map_err(|error| DatabaseError::GenericCreateError(error.to_string()))
+    }
+
+    async fn delete_by_expiry(&self, table_name: &str) -> Result<Vec<String>, DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(Vec::new());
+        };
+        let mut removed = Vec::new();
+        for path in pool.session_files(table_name).await? {
+            let expired = FileSessionPool::read(&path)
+                .await
+                .is_none_or(|stored| is_expired(&stored));
+            if expired && fs::remove_file(&path).await.is_ok() {
+                removed.extend(session_id(&path));
+            }
+        }
+        Ok(removed)
+    }
+
+    async fn count(&self, table_name: &str) -> Result<i64, DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(0);
+        };
+        Ok(pool.session_files(table_name).await?.len() as i64)
+    }
+
+    async fn store(
+        &self,
+        id: &str,
+        session: &str,
+        expires: i64,
+        table_name: &str,
+    ) -> Result<(), DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(());
+        };
+        let path = pool.session_path(id, table_name)?;
+        let text = serde_json::to_string(&StoredSession {
+            expires,
+            session: session.to_string(),
+        })
+        .map_err(|error| DatabaseError::GenericInsertError(error.to_string()))?;
+
+        let temp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            WRITE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = match fs::write(&temp_path, text).await {
+            Ok(()) => fs::rename(&temp_path, &path).await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = written {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(DatabaseError::GenericInsertError(error.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn load(&self, id: &str, table_name: &str) -> Result<Option<String>, DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(None);
+        };
+        let path = pool.session_path(id, table_name)?;
+        let Some(stored) = FileSessionPool::read(&path).await else {
+            return Ok(None);
+        };
+        if is_expired(&stored) {
+            let _ = fs::remove_file(&path).await;
+            return Ok(None);
+        }
+        Ok(Some(stored.session))
+    }
+
+    async fn delete_one_by_id(&self, id: &str, table_name: &str) -> Result<(), DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(());
+        };
+        match fs::remove_file(pool.session_path(id, table_name)?).await {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                Err(DatabaseError::GenericDeleteError(error.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn exists(&self, id: &str, table_name: &str) -> Result<bool, DatabaseError> {
+        Ok(self.load(id, table_name).await?.is_some())
+    }
+
+    async fn delete_all(&self, table_name: &str) -> Result<(), DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(());
+        };
+        for path in pool.session_files(table_name).await? {
+            fs::remove_file(&path)
+                .await
+                .map_err(|error| DatabaseError::GenericDeleteError(error.to_string()))?;
+        }
+        Ok(())
+    }
+
+    async fn get_ids(&self, table_name: &str) -> Result<Vec<String>, DatabaseError> {
+        let Self::File(pool) = self else {
+            return Ok(Vec::new());
+        };
+        Ok(pool
+            .session_files(table_name)
+            .await?
+            .iter()
+            .filter_map(|path| session_id(path))
+            .collect())
+    }
+
+    fn auto_handles_expiry(&self) -> bool {
+        false
+    }
+}
+
+impl SessionPool {
+    fn from_settings(settings: &SessionSettings) -> Self {
+        match &settings.store {
+            SessionStoreKind::Memory => Self::Memory,
+            SessionStoreKind::File(dir) => Self::File(FileSessionPool::new(dir)),
+        }
+    }
+}
+
+/// Builds the session store described by the site configuration. axum_session
+/// purges expired sessions from memory and, through `delete_by_expiry`, from
+/// disk every `cleanup_minutes`, checked as requests come in.
+pub async fn init_session_store(config: &SiteConfig) -> Result<SessionStore<SessionPool>, String> {
+    let settings = &config.session;
+    let lifetime = chrono::Duration::days(i64::from(settings.lifetime_days));
+    let session_config = SessionConfig::default()
+        .with_session_name(SESSION_COOKIE_NAME)
+        .with_table_name(SESSION_TABLE_NAME)
+        .with_mode(SessionMode::OptIn)
+        .with_cookie_same_site(SameSite::Lax)
+        .with_http_only(true)
+        .with_secure(config.secure_cookies)
+        .with_lifetime(lifetime)
+        .with_max_age(Some(lifetime))
+        .with_purge_update(chrono::Duration::minutes(i64::from(
+            settings.cleanup_minutes,
+        )));
+
+    SessionStore::new(Some(SessionPool::from_settings(settings)), session_config)
+        .await
+        .map_err(|error| format!("failed to initialize session store: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(name: &str) -> SessionPool {
+        let dir = std::env::temp_dir().join(format!("sessions-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        SessionPool::File(FileSessionPool::new(dir))
+    }
+
+    fn in_hours(hours: i64) -> i64 {
+        Utc::now().timestamp() + hours * 60 * 60
+    }
+
+    #[tokio::test]
+    async fn stores_and_loads_sessions() {
+        let pool = pool("roundtrip");
+        pool.initiate("t").await.unwrap();
+        pool.store("abc", "data", in_hours(1), "t").await.unwrap();
+        assert_eq!(
+            pool.load("abc", "t").await.unwrap().as_deref(),
+            Some("data")
+        );
+        assert!(pool.load("../abc", "t").await.is_err());
+        assert_eq!(pool.get_ids("t").await.unwrap(), ["abc"]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_writes_to_one_session_all_succeed() {
+        let pool = pool("concurrent");
+        pool.initiate("t").await.unwrap();
+        let writes = (0..8).map(|index| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                pool.store("abc", &index.to_string(), in_hours(1), "t")
+                    .await
+            })
+        });
+        for write in writes.collect::<Vec<_>>() {
+            write.await.unwrap().unwrap();
+        }
+        assert_eq!(pool.count("t").await.unwrap(), 1);
+        assert!(pool.load("abc", "t").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn purges_only_expired_sessions() {
+        let pool = pool("expiry");
+        pool.initiate("t").await.unwrap();
+        pool.store("old", "data", in_hours(-1), "t").await.unwrap();
+        pool.store("new", "data", in_hours(1), "t").await.unwrap();
+        assert_eq!(pool.delete_by_expiry("t").await.unwrap(), ["old"]);
+        assert_eq!(pool.get_ids("t").await.unwrap(), ["new"]);
+    }
+}
@@ -0,0 +1,27 @@
+/// Builds a self-closing `<Name attr="..."/>` tag for the markdown renderer's
+/// custom components. Values are percent-encoded so they survive markdown
+/// parsing; components decode them with `utils::url::percent_decode`.
+pub fn component_tag(name: &str, attributes: &[(&str, &str)]) -> String {
+    let mut tag = format!("<{name}");
+    for (key, value) in attributes {
+        tag.push(' ');
+        tag.push_str(key);
+        tag.push_str("=\"");
+        tag.push_str(&encode_attribute(value));
+        tag.push('"');
+    }
+    tag.push_str("/>");
+    tag
+}
+
+fn encode_attribute(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '%' | '+' | '"' | '\'' | '<' | '>' | '&' | '`' | '$' | '\\' | '*' | '_' | '[' | ']'
+            | '\r' | '\n' => encoded.push_str(&format!("%{:02X}", ch as u32)),
+            _ => encoded.push(ch),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,406 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+use crate::impls::site::types::{LocaleInfo, SiteInfo, SocialLink};
+
+const CONFIG_PATH_ENV: &str = "SITE_CONFIG";
+const DEFAULT_CONFIG_PATH: &str = "site.toml";
+const DEFAULT_SESSION_DIR: &str = "data/sessions";
+
+/// Site settings from `site.toml` (or `SITE_CONFIG`), with environment
+/// variables taking precedence over the file.
+#[derive(Debug, Clone)]
+pub struct SiteConfig {
+    pub info: SiteInfo,
+    /// Only set when configured explicitly, so an embedded content tree can
+    /// still be preferred over the default `content` directory.
+    pub content_dir: Option<PathBuf>,
+    pub secure_cookies: bool,
+    /// Serve a `robots.txt` that disallows everything, for staging deploys.
+    /// A `robots.txt` in the content directory still takes precedence.
+    pub robots_disallow_all: bool,
+    pub session: SessionSettings,
+}
+
+/// Where preference sessions are kept and for how long.
+#[derive(Debug, Clone)]
+pub struct SessionSettings {
+    pub store: SessionStoreKind,
+    pub lifetime_days: u32,
+    pub cleanup_minutes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStoreKind {
+    /// Lost on restart.
+    Memory,
+    /// One JSON file per session under this directory.
+    File(PathBuf),
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self {
+            store: SessionStoreKind::Memory,
+            lifetime_days: 30,
+            cleanup_minutes: 60,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawSessionConfig {
+    store: Option<String>,
+    dir: Option<PathBuf>,
+    lifetime_days: Option<u32>,
+    cleanup_minutes: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawSiteConfig {
+    name: Option<String>,
+    url: Option<String>,
+    mermaid_url: Option<String>,
+    locales: Option<Vec<LocaleInfo>>,
+    default_locale: Option<String>,
+    content_dir: Option<PathBuf>,
+    secure_cookies: Option<bool>,
+    robots_disallow_all: Option<bool>,
+    social_links: Option<Vec<SocialLink>>,
+    session: RawSessionConfig,
+}
+
+static SITE_CONFIG: OnceLock<SiteConfig> = OnceLock::new();
+
+/// Loads and validates the configuration. Called once at startup so a bad
+/// `site.toml` stops the server instead of surfacing later.
+pub fn init_site_config() -> Result<&'static SiteConfig, String> {
+    if let Some(config) = SITE_CONFIG.get() {
+        return Ok(config);
+    }
+    let config = load_site_config()?;
+    Ok(SITE_CONFIG.get_or_init(|| config))
+}
+
+/// The configuration checked by [`init_site_config`] at startup. Anything that
+/// runs without that check, like tests, still fails loudly on a bad file.
+pub fn site_config() -> &'static SiteConfig {
+    SITE_CONFIG.get_or_init(|| load_site_config().unwrap_or_else(|error| panic!("{error}")))
+}
+
+fn load_site_config() -> Result<SiteConfig, String> {
+    let mut raw = read_config_file()?;
+    let enabled_locales = apply_env_overrides(&mut raw)?;
+    build_site_config(raw, enabled_locales)
+}
+
+/// Fills in defaults and validates the result. `enabled_locales` narrows the
+/// configured locales, as `SITE_LOCALES` does.
+fn build_site_config(
+    raw: RawSiteConfig,
+    enabled_locales: Option<Vec<String>>,
+) -> Result<SiteConfig, String> {
+    let mut locales = required(raw.locales, "[[locales]]")?;
+    if let Some(codes) = enabled_locales {
+        locales = select_locales(locales, &codes)?;
+    }
+    let config = SiteConfig {
+        info: SiteInfo {
+            name: required(raw.name, "name")?,
+            url: required(raw.url, "url")?
+                .trim()
+                .trim_end_matches('/')
+                .to_string(),
+            mermaid_url: raw.mermaid_url,
+            default_locale: raw
+                .default_locale
+                .or_else(|| locales.first().map(|locale| locale.code.clone()))
+                .unwrap_or_default(),
+            locales,
+            social_links: raw.social_links.unwrap_or_default(),
+        },
+        content_dir: raw.content_dir,
+        secure_cookies: raw.secure_cookies.unwrap_or_else(is_production),
+        robots_disallow_all: raw.robots_disallow_all.unwrap_or(false),
+        session: session_settings(raw.session, SessionSettings::default())?,
+    };
+
+    validate(&config)?;
+    Ok(config)
+}
+
+fn required<T>(value: Option<T>, key: &str) -> Result<T, String> {
+    value.ok_or_else(|| format!("invalid site configuration: {key} is not set"))
+}
+
+fn session_settings(
+    raw: RawSessionConfig,
+    defaults: SessionSettings,
+) -> Result<SessionSettings, String> {
+    let store = match raw.store.as_deref() {
+        None | Some("memory") => SessionStoreKind::Memory,
+        Some("file") => SessionStoreKind::File(
+            raw.dir
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_SESSION_DIR)),
+        ),
+        Some(other) => {
+            return Err(format!(
+                "session.store must be \"memory\" or \"file\", got '{other}'"
+            ))
+        }
+    };
+    Ok(SessionSettings {
+        store,
+        lifetime_days: raw.lifetime_days.unwrap_or(defaults.lifetime_days),
+        cleanup_minutes: raw.cleanup_minutes.unwrap_or(defaults.cleanup_minutes),
+    })
+}
+
+// The site identity and its locales only live in the file, so it must exist.
+fn read_config_file() -> Result<RawSiteConfig, String> {
+    let path = std::env::var(CONFIG_PATH_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_CONFIG_PATH));
+    let text = fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read {}: {error}", path.display()))?;
+    toml::from_str(&text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Applies environment overrides to `raw`. `SITE_LOCALES` narrows the
+/// configured locales rather than defining new ones, so its codes are
+/// returned for [`select_locales`].
+fn apply_env_overrides(raw: &mut RawSiteConfig) -> Result<Option<Vec<String>>, String> {
+    if let Some(value) = env_value("SITE_NAME") {
+        raw.name = Some(value);
+    }
+    if let Some(value) = env_value("SITE_URL") {
+        raw.url = Some(value);
+    }
+    if let Some(value) = env_value("MERMAID_URL") {
+        raw.mermaid_url = Some(value);
+    }
+    let enabled_locales = env_value("SITE_LOCALES").map(|value| {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|locale| !locale.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+    if let Some(value) = env_value("SITE_DEFAULT_LOCALE") {
+        raw.default_locale = Some(value);
+    }
+    if let Some(value) = env_value("CONTENT_DIR") {
+        raw.content_dir = Some(PathBuf::from(value));
+    }
+    if let Some(value) = env_value("SECURE_COOKIES") {
+        let secure = value
+            .parse::<bool>()
+            .map_err(|_| format!("SECURE_COOKIES must be true or false, got '{value}'"))?;
+        raw.secure_cookies = Some(secure);
+    }
+    if let Some(value) = env_value("ROBOTS_DISALLOW_ALL") {
+        let disallow = value
+            .parse::<bool>()
+            .map_err(|_| format!("ROBOTS_DISALLOW_ALL must be true or false, got '{value}'"))?;
+        raw.robots_disallow_all = Some(disallow);
+    }
+    if let Some(value) = env_value("SESSION_STORE") {
+        raw.session.store = Some(value);
+    }
+    if let Some(value) = env_value("SESSION_DIR") {
+        raw.session.dir = Some(PathBuf::from(value));
+    }
+    if let Some(value) = env_value("SESSION_LIFETIME_DAYS") {
+        let days = value
+            .parse::<u32>()
+            .map_err(|_| format!("SESSION_LIFETIME_DAYS must be a number, got '{value}'"))?;
+        raw.session.lifetime_days = Some(days);
+    }
+    if let Some(value) = env_value("SESSION_CLEANUP_MINUTES") {
+        let minutes = value
+            .parse::<u32>()
+            .map_err(|_| format!("SESSION_CLEANUP_MINUTES must be a number, got '{value}'"))?;
+        raw.session.cleanup_minutes = Some(minutes);
+    }
+    Ok(enabled_locales)
+}
+
+/// The configured locales named by `codes`, in that order.
+fn select_locales(locales: Vec<LocaleInfo>, codes: &[String]) -> Result<Vec<LocaleInfo>, String> {
+    codes
+        .iter()
+        .map(|code| {
+            locales
+                .iter()
+                .find(|locale| &locale.code == code)
+                .cloned()
+                .ok_or_else(|| {
+                    format!("SITE_LOCALES names '{code}', which has no [[locales]] entry")
+                })
+        })
+        .collect()
+}
+
+fn validate(config: &SiteConfig) -> Result<(), String> {
+    let info = &config.info;
+    let mut problems = Vec::new();
+
+    if info.name.trim().is_empty() {
+        problems.push("name must not be empty".to_string());
+    }
+    if !is_http_url(&info.url) {
+        problems.push(format!(
+            "url must be an absolute http(s) URL, got '{}'",
+            info.url
+        ));
+    }
+    if let Some(url) = info.mermaid_url.as_ref() {
+        if !is_http_url(url) && !url.starts_with('/') {
+            problems.push(format!(
+                "mermaid_url must be an http(s) URL or an absolute path, got '{url}'"
+            ));
+        }
+    }
+    problems.extend(locale_problems(&info.locales, &info.default_locale));
+    for link in info.social_links.iter() {
+        if !is_http_url(&link.url) {
+            problems.push(format!("social link URL '{}' is not http(s)", link.url));
+        }
+    }
+    if config.session.lifetime_days == 0 {
+        problems.push("session.lifetime_days must be at least 1".to_string());
+    }
+    if config.session.cleanup_minutes == 0 {
+        problems.push("session.cleanup_minutes must be at least 1".to_string());
+    }
+    if let Some(dir) = config.content_dir.as_ref() {
+        if !dir.is_dir() {
+            problems.push(format!("content_dir {} is not a directory", dir.display()));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid site configuration: {}",
+            problems.join("; ")
+        ))
+    }
+}
+
+// Fallbacks may name locales `SITE_LOCALES` left out; the registry skips them.
+fn locale_problems(locales: &[LocaleInfo], default_locale: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    if locales.is_empty() {
+        problems.push("locales must list at least one locale".to_string());
+    }
+
+    let codes = locales
+        .iter()
+        .map(|locale| locale.code.as_str())
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    for locale in locales {
+        let code = &locale.code;
+        if code.is_empty()
+            || !code
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        {
+            problems.push(format!(
+                "locale code '{code}' must be letters, digits or '-'"
+            ));
+        }
+        if !seen.insert(code.as_str()) {
+            problems.push(format!("locale '{code}' is listed twice"));
+        }
+        if locale.tag.parse::<LanguageIdentifier>().is_err() {
+            problems.push(format!(
+                "locale '{code}' has an invalid language tag '{}'",
+                locale.tag
+            ));
+        }
+        if locale.name.trim().is_empty() {
+            problems.push(format!("locale '{code}' needs a name"));
+        }
+    }
+    if !codes.contains(&default_locale) {
+        problems.push(format!(
+            "default_locale '{default_locale}' is not one of locales {codes:?}"
+        ));
+    }
+    problems
+}
+
+fn is_http_url(value: &str) -> bool {
+    let rest = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"));
+    rest.is_some_and(|rest| !rest.is_empty())
+}
+
+fn is_production() -> bool {
+    std::env::var("APP_ENV").ok().as_deref() == Some("production")
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(code: &str, tag: &str) -> LocaleInfo {
+        LocaleInfo {
+            code: code.to_string(),
+            tag: tag.to_string(),
+            name: code.to_string(),
+            fallback: vec!["cn".to_string()],
+            ftl: String::new(),
+        }
+    }
+
+    #[test]
+    fn bundled_site_toml_is_complete() {
+        let text = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/site.toml"));
+        let raw = toml::from_str::<RawSiteConfig>(text).expect("site.toml parses");
+        let config = build_site_config(raw, None).expect("site.toml is valid");
+        assert!(!config.info.locales.is_empty());
+        assert!(required::<String>(None, "name").is_err());
+    }
+
+    #[test]
+    fn site_locales_selects_and_orders_configured_locales() {
+        let configured = vec![locale("cn", "zh-CN"), locale("en", "en-US")];
+        let selected = select_locales(configured.clone(), &["en".to_string()]).unwrap();
+        assert_eq!(selected, vec![locale("en", "en-US")]);
+        assert!(select_locales(configured, &["ja".to_string()]).is_err());
+    }
+
+    #[test]
+    fn reports_invalid_locales() {
+        assert!(locale_problems(&[locale("cn", "zh-CN"), locale("en", "en-US")], "cn").is_empty());
+        // A fallback outside the enabled set is skipped, not rejected.
+        assert!(locale_problems(&[locale("en", "en-US")], "en").is_empty());
+
+        assert_eq!(locale_problems(&[], "cn").len(), 2);
+        assert_eq!(locale_problems(&[locale("cn", "not a tag")], "cn").len(), 1);
+        assert_eq!(
+            locale_problems(&[locale("cn", "zh-CN"), locale("cn", "zh-CN")], "cn").len(),
+            1
+        );
+        assert_eq!(locale_problems(&[locale("en", "en-US")], "cn").len(), 1);
+    }
+}
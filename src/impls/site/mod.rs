@@ -0,0 +1,19 @@
+#[cfg(feature = "server")]
+mod component;
+#[cfg(feature = "server")]
+mod config;
+#[cfg(feature = "server")]
+mod service;
+mod types;
+#[cfg(feature = "server")]
+mod xml;
+
+#[cfg(feature = "server")]
+pub use component::component_tag;
+#[cfg(feature = "server")]
+pub use config::{init_site_config, site_config, SessionSettings, SessionStoreKind, SiteConfig};
+#[cfg(feature = "server")]
+pub use service::{hreflang, site_info, site_title, site_url};
+pub use types::{LocaleInfo, SiteInfo, SocialKind, SocialLink};
+#[cfg(feature = "server")]
+pub use xml::escape_xml;
@@ -0,0 +1,48 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::impls::blog;
+use crate::impls::i18n;
+use crate::impls::site::config::site_config;
+use crate::impls::site::types::SiteInfo;
+
+/// Relative to the content root.
+const LOCALES_DIR: &str = "locales";
+
+pub fn site_url() -> String {
+    site_config().info.url.clone()
+}
+
+pub fn site_title() -> String {
+    site_config().info.name.clone()
+}
+
+pub fn hreflang(lang: &str) -> String {
+    i18n::find_locale(lang).map_or_else(|| lang.to_string(), |locale| locale.tag())
+}
+
+/// The site settings with every locale's messages loaded from
+/// `locales/{tag}.ftl` in the content source. A missing file leaves that
+/// language without translated UI strings.
+pub fn site_info() -> SiteInfo {
+    static INFO: OnceLock<SiteInfo> = OnceLock::new();
+    INFO.get_or_init(|| {
+        let mut info = site_config().info.clone();
+        let source = blog::content_source();
+        for locale in info.locales.iter_mut() {
+            let path = Path::new(LOCALES_DIR).join(format!("{}.ftl", locale.tag));
+            match source.read_to_string(&path) {
+                Ok(ftl) => locale.ftl = ftl,
+                Err(error) => {
+                    log::warn!(
+                        "failed to read {}: {error}",
+                        source.display_path(&path).display()
+                    );
+                    locale.ftl.clear();
+                }
+            }
+        }
+        info
+    })
+    .clone()
+}
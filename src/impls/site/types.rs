@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// The part of the site configuration components need. Served to the client
+/// by `IO::site::get_site_info` and provided through context in `root::App`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteInfo {
+    pub name: String,
+    pub url: String,
+    /// Replaces the bundled Mermaid script when set.
+    pub mermaid_url: Option<String>,
+    /// Enabled languages, in the order the language switcher cycles through.
+    pub locales: Vec<LocaleInfo>,
+    pub default_locale: String,
+    pub social_links: Vec<SocialLink>,
+}
+
+/// One `[[locales]]` entry of `site.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocaleInfo {
+    /// Short code used in URLs, content file names and the session.
+    pub code: String,
+    /// BCP 47 tag, e.g. `zh-CN`; also names the `.ftl` file.
+    pub tag: String,
+    /// Name shown in the language switcher, written in the language itself.
+    pub name: String,
+    /// Codes tried in order when content is missing in this language.
+    #[serde(default)]
+    pub fallback: Vec<String>,
+    /// Fluent messages. Filled in from `locales/{tag}.ftl` in the content
+    /// source; anything set in `site.toml` is replaced.
+    #[serde(default)]
+    pub ftl: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocialLink {
+    pub kind: SocialKind,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SocialKind {
+    Github,
+    Linkedin,
+    #[serde(alias = "x")]
+    Twitter,
+}
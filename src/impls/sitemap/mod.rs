@@ -0,0 +1,9 @@
+#[cfg(feature = "server")]
+mod routes;
+#[cfg(feature = "server")]
+mod service;
+
+#[cfg(feature = "server")]
+pub use routes::router;
+#[cfg(feature = "server")]
+pub use service::get_sitemap_documents;
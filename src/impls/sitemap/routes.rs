@@ -0,0 +1,28 @@
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+
+use crate::impls::sitemap::service::{build_robots_txt, build_sitemap};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(sitemap))
+        .route("/robots.txt", get(robots))
+}
+
+async fn sitemap() -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        build_sitemap(),
+    )
+        .into_response()
+}
+
+async fn robots() -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        build_robots_txt(),
+    )
+        .into_response()
+}
@@ -0,0 +1,178 @@
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::impls::blog::{self, PostMetadata};
+use crate::impls::locale_routes::{localized_path, split_locale_prefix};
+use crate::impls::site::{escape_xml, hreflang, site_config, site_url};
+
+/// Relative to the content root.
+const ROBOTS_PATH: &str = "robots.txt";
+
+struct SitemapEntry {
+    path: String,
+    lastmod: Option<NaiveDate>,
+    langs: Vec<String>,
+    /// Whether each language has its own `/{lang}{path}` URL.
+    localized: bool,
+}
+
+pub fn build_sitemap() -> String {
+    let site = site_url();
+    let langs = blog::get_available_languages();
+    let posts = langs
+        .iter()
+        .flat_map(|lang| blog::get_posts_by_lang(lang))
+        .collect::<Vec<_>>();
+
+    let mut entries = vec![SitemapEntry {
+        path: "/about".to_string(),
+        lastmod: None,
+        langs: langs.clone(),
+        localized: false,
+    }];
+
+    let mut langs_by_path = BTreeMap::<String, Vec<String>>::new();
+    for route in blog::get_static_routes() {
+        if let Some((lang, path)) = split_locale_prefix(&route) {
+            langs_by_path
+                .entry(path.to_string())
+                .or_default()
+                .push(lang.to_string());
+        }
+    }
+
+    for (path, path_langs) in langs_by_path {
+        let matching = posts
+            .iter()
+            .filter(|meta| path_langs.contains(&meta.lang))
+            .filter(|meta| {
+                if let Some(slug) = post_slug(&path) {
+                    meta.slug == slug
+                } else if let Some(tag_id) = tag_id(&path) {
+                    has_tag(meta, tag_id)
+                } else if let Some(series_id) = series_id(&path) {
+                    has_series(meta, series_id)
+                } else {
+                    true
+                }
+            });
+        entries.push(SitemapEntry {
+            lastmod: latest_date(matching),
+            langs: path_langs,
+            localized: true,
+            path,
+        });
+    }
+
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">"#);
+    xml.push('\n');
+    for entry in entries.iter() {
+        render_entry(&mut xml, &site, entry);
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+pub fn build_robots_txt() -> String {
+    let site = site_url();
+    if let Ok(text) = blog::content_source().read_to_string(Path::new(ROBOTS_PATH)) {
+        return text.replace("{site_url}", &site);
+    }
+
+    if site_config().robots_disallow_all {
+        return "User-agent: *\nDisallow: /\n".to_string();
+    }
+
+    format!("User-agent: *\nAllow: /\nDisallow: /api/\n\nSitemap: {site}/sitemap.xml\n")
+}
+
+/// `robots.txt` and `sitemap.xml` as `(path, body)`.
+pub fn get_sitemap_documents() -> Vec<(String, String)> {
+    vec![
+        ("/robots.txt".to_string(), build_robots_txt()),
+        ("/sitemap.xml".to_string(), build_sitemap()),
+    ]
+}
+
+fn render_entry(xml: &mut String, site: &str, entry: &SitemapEntry) {
+    let default_url = escape_xml(&format!("{site}{}", entry.path));
+    if !entry.localized {
+        render_url(xml, &default_url, &default_url, entry, |_| {
+            default_url.clone()
+        });
+        return;
+    }
+
+    let lang_url = |lang: &str| escape_xml(&format!("{site}{}", localized_path(lang, &entry.path)));
+    for lang in entry.langs.iter() {
+        render_url(xml, &lang_url(lang), &default_url, entry, lang_url);
+    }
+}
+
+/// One `<url>` with `hreflang` alternates for each of the entry's languages;
+/// `x-default` points at the unprefixed path, which redirects by preference.
+fn render_url(
+    xml: &mut String,
+    loc: &str,
+    default_url: &str,
+    entry: &SitemapEntry,
+    alternate: impl Fn(&str) -> String,
+) {
+    xml.push_str("  <url>\n");
+    xml.push_str(&format!("    <loc>{loc}</loc>\n"));
+    if let Some(lastmod) = entry.lastmod {
+        xml.push_str(&format!(
+            "    <lastmod>{}</lastmod>\n",
+            lastmod.format("%Y-%m-%d")
+        ));
+    }
+    if entry.langs.len() > 1 {
+        for lang in entry.langs.iter() {
+            xml.push_str(&format!(
+                r#"    <xhtml:link rel="alternate" hreflang="{}" href="{}"/>"#,
+                hreflang(lang),
+                alternate(lang)
+            ));
+            xml.push('\n');
+        }
+        xml.push_str(&format!(
+            r#"    <xhtml:link rel="alternate" hreflang="x-default" href="{default_url}"/>"#
+        ));
+        xml.push('\n');
+    }
+    xml.push_str("  </url>\n");
+}
+
+fn post_slug(path: &str) -> Option<&str> {
+    path.strip_prefix("/blog/")
+        .and_then(|rest| rest.strip_suffix('/'))
+        .filter(|slug| !slug.is_empty())
+}
+
+fn tag_id(path: &str) -> Option<&str> {
+    path.strip_prefix("/tags/").filter(|tag| !tag.is_empty())
+}
+
+fn series_id(path: &str) -> Option<&str> {
+    path.strip_prefix("/series/").filter(|id| !id.is_empty())
+}
+
+fn has_series(meta: &PostMetadata, series_id: &str) -> bool {
+    meta.series
+        .as_ref()
+        .is_some_and(|series| series.id == series_id)
+}
+
+fn has_tag(meta: &PostMetadata, tag_id: &str) -> bool {
+    meta.tags.iter().flatten().any(|tag| tag.id == tag_id)
+}
+
+fn latest_date<'a>(posts: impl Iterator<Item = &'a PostMetadata>) -> Option<NaiveDate> {
+    posts
+        .filter_map(|meta| NaiveDate::parse_from_str(&meta.date, "%Y-%m-%d").ok())
+        .max()
+}
@@ -0,0 +1,5 @@
+mod registry;
+
+pub use registry::{
+    resolve_theme, resolve_theme_preference, themes, Appearance, ThemeDef, SYSTEM_THEME,
+};
@@ -0,0 +1,132 @@
+/// Whether a theme is light or dark. It becomes the class on `<html>`, so
+/// `dark:` utilities keep working for every palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+}
+
+/// A color theme. Adding one takes a row in [`THEMES`] and a
+/// `:root[data-theme="{id}"]` palette in `assets/theme.css`; the switcher
+/// picks it up.
+#[derive(Debug, PartialEq)]
+pub struct ThemeDef {
+    /// Stored in the session and written to `data-theme` on `<html>`.
+    pub id: &'static str,
+    /// Label shown in the theme switcher.
+    pub name: &'static str,
+    pub appearance: Appearance,
+    /// Syntect theme used for code blocks.
+    pub syntax_theme: &'static str,
+    /// Mermaid theme, read by `mermaid_bridge.js` from `data-mermaid-theme`.
+    pub mermaid_theme: &'static str,
+}
+
+/// Preference value that follows the OS color scheme between the `light`
+/// and `dark` themes.
+pub const SYSTEM_THEME: &str = "system";
+
+static THEMES: &[ThemeDef] = &[
+    ThemeDef {
+        id: "light",
+        name: "Light",
+        appearance: Appearance::Light,
+        syntax_theme: "base16-ocean.light",
+        mermaid_theme: "base",
+    },
+    ThemeDef {
+        id: "dark",
+        name: "Dark",
+        appearance: Appearance::Dark,
+        syntax_theme: "base16-ocean.dark",
+        mermaid_theme: "dark",
+    },
+    ThemeDef {
+        id: "sepia",
+        name: "Sepia",
+        appearance: Appearance::Light,
+        syntax_theme: "InspiredGitHub",
+        mermaid_theme: "neutral",
+    },
+    ThemeDef {
+        id: "high-contrast",
+        name: "High contrast",
+        appearance: Appearance::Dark,
+        syntax_theme: "base16-eighties.dark",
+        mermaid_theme: "dark",
+    },
+    ThemeDef {
+        id: "solarized-light",
+        name: "Solarized light",
+        appearance: Appearance::Light,
+        syntax_theme: "Solarized (light)",
+        mermaid_theme: "neutral",
+    },
+    ThemeDef {
+        id: "solarized-dark",
+        name: "Solarized dark",
+        appearance: Appearance::Dark,
+        syntax_theme: "Solarized (dark)",
+        mermaid_theme: "dark",
+    },
+];
+
+pub fn themes() -> &'static [ThemeDef] {
+    THEMES
+}
+
+pub fn default_theme() -> &'static ThemeDef {
+    &THEMES[0]
+}
+
+pub fn find_theme(id: &str) -> Option<&'static ThemeDef> {
+    THEMES.iter().find(|theme| theme.id == id.trim())
+}
+
+/// The stored theme preference: a theme id or [`SYSTEM_THEME`], falling
+/// back to the default theme for unknown values.
+pub fn resolve_theme_preference(theme: Option<&str>) -> &'static str {
+    match theme {
+        Some(SYSTEM_THEME) => SYSTEM_THEME,
+        _ => theme.and_then(find_theme).unwrap_or_else(default_theme).id,
+    }
+}
+
+/// The theme rendered for a preference. `color_scheme` is the browser's
+/// `prefers-color-scheme` ("light" or "dark"), used by [`SYSTEM_THEME`].
+pub fn resolve_theme(theme: Option<&str>, color_scheme: Option<&str>) -> &'static ThemeDef {
+    match resolve_theme_preference(theme) {
+        SYSTEM_THEME => color_scheme
+            .filter(|scheme| *scheme == Appearance::Dark.as_str())
+            .and_then(find_theme)
+            .unwrap_or_else(default_theme),
+        id => find_theme(id).unwrap_or_else(default_theme),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_theme_has_a_palette() {
+        let stylesheet = include_str!("../../../assets/theme.css");
+        for theme in themes() {
+            let selector = format!(":root[data-theme=\"{}\"]", theme.id);
+            assert!(
+                stylesheet.contains(&selector),
+                "no palette for {}",
+                theme.id
+            );
+        }
+    }
+}
@@ -22,33 +22,23 @@ const MERMAID_BASE_CONFIG = {
   securityLevel: "loose",
   startOnLoad: false,
 };
-let current_app_theme = null;
 let current_mermaid_theme = null;
 let is_theme_listener_bound = false;
 
-function resolve_app_theme(theme) {
-  return theme === "dark" ? "dark" : "light";
-}
-
-function map_mermaid_theme(app_theme) {
-  return app_theme === "dark" ? "redux-dark" : "base";
-}
-
-function read_document_theme() {
+// The theme registry maps each app theme to a Mermaid theme; the bootstrap
+// script and `js_apply_theme` expose it as `data-mermaid-theme`.
+function read_document_mermaid_theme() {
   const root = document.documentElement;
-  if (root && root.classList.contains("dark")) return "dark";
-  return "light";
+  return root?.dataset?.mermaidTheme || "base";
 }
 
-function apply_mermaid_theme(mermaid, app_theme) {
-  const next_theme = map_mermaid_theme(app_theme);
-  if (current_mermaid_theme === next_theme) return false;
+function apply_mermaid_theme(mermaid, mermaid_theme) {
+  if (current_mermaid_theme === mermaid_theme) return false;
   mermaid.initialize({
     ...MERMAID_BASE_CONFIG,
-    theme: next_theme,
+    theme: mermaid_theme,
   });
-  current_app_theme = app_theme;
-  current_mermaid_theme = next_theme;
+  current_mermaid_theme = mermaid_theme;
   return true;
 }
 
@@ -62,8 +52,7 @@ async function rerender_all_diagrams() {
 function bind_theme_change_listener() {
   if (is_theme_listener_bound) return;
   document.addEventListener("app:theme-changed", (event) => {
-    const app_theme = resolve_app_theme(event?.detail?.theme);
-    void js_set_mermaid_theme(app_theme);
+    void js_set_mermaid_theme(event?.detail?.mermaid_theme);
   });
   is_theme_listener_bound = true;
 }
@@ -87,8 +76,8 @@ function set_error(output, error) {
 export async function js_set_mermaid_theme(theme) {
   const mermaid = await wait_mermaid();
   if (!mermaid) return null;
-  const app_theme = resolve_app_theme(theme);
-  const theme_changed = apply_mermaid_theme(mermaid, app_theme);
+  const mermaid_theme = theme || read_document_mermaid_theme();
+  const theme_changed = apply_mermaid_theme(mermaid, mermaid_theme);
   if (!theme_changed) return current_mermaid_theme;
   await rerender_all_diagrams();
   return current_mermaid_theme;
@@ -102,8 +91,10 @@ export async function js_render_mermaid_by_base_id(base_id) {
 
   const mermaid = await wait_mermaid();
   if (!mermaid) return null;
-  const app_theme = current_app_theme ?? read_document_theme();
-  apply_mermaid_theme(mermaid, app_theme);
+  apply_mermaid_theme(
+    mermaid,
+    current_mermaid_theme ?? read_document_mermaid_theme()
+  );
   if (!is_current_render(base_id, version)) return null;
 
   const source = await wait_element(`${base_id}-src`);
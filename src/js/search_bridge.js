@@ -0,0 +1,10 @@
+export async function js_fetch_text(url) {
+  if (!url) return null;
+  try {
+    const response = await fetch(url, { credentials: "same-origin" });
+    if (!response.ok) return null;
+    return await response.text();
+  } catch (_) {
+    return null;
+  }
+}
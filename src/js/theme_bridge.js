@@ -1,10 +1,48 @@
-export function js_apply_theme(theme) {
+const SYSTEM_DARK_QUERY = "(prefers-color-scheme: dark)";
+const SYSTEM_THEME_COOKIE = "prefers_color_scheme";
+
+function system_dark_query() {
+  return window.matchMedia ? window.matchMedia(SYSTEM_DARK_QUERY) : null;
+}
+
+function read_system_theme() {
+  const query = system_dark_query();
+  const theme = query && query.matches ? "dark" : "light";
+  document.cookie = `${SYSTEM_THEME_COOKIE}=${theme}; path=/; max-age=31536000; samesite=lax`;
+  return theme;
+}
+
+export function js_system_theme() {
+  return read_system_theme();
+}
+
+// Resolves with the new color scheme the next time the OS setting changes.
+// Rejects right away when the browser cannot report it, so callers stop
+// waiting instead of hanging on a promise that never settles.
+export function js_next_system_theme() {
+  return new Promise((resolve, reject) => {
+    const query = system_dark_query();
+    if (!query) {
+      reject(new Error("matchMedia is not available"));
+      return;
+    }
+    query.addEventListener("change", () => resolve(read_system_theme()), {
+      once: true,
+    });
+  });
+}
+
+export function js_apply_theme(theme, appearance, mermaid_theme) {
   if (!theme) return;
   const root = document.documentElement;
-  root.setAttribute("class", theme);
+  root.classList.remove("light", "dark");
+  root.classList.add(appearance);
+  root.setAttribute("data-theme", theme);
+  root.setAttribute("data-mermaid-theme", mermaid_theme);
+  root.removeAttribute("data-theme-pending");
   document.dispatchEvent(
     new CustomEvent("app:theme-changed", {
-      detail: { theme },
+      detail: { theme, appearance, mermaid_theme },
     })
   );
 }
@@ -2,6 +2,8 @@ use dioxus::prelude::*;
 #[allow(non_snake_case)]
 mod IO;
 
+#[cfg(feature = "server")]
+mod cli;
 mod components;
 mod impls;
 mod root;
@@ -12,25 +14,40 @@ use crate::root::App;
 #[cfg(feature = "server")]
 #[tokio::main]
 async fn main() {
-    use axum_session::{SameSite, SessionConfig, SessionLayer, SessionMode, SessionStore};
+    use axum_session::SessionLayer;
     use dioxus_server::DioxusRouterExt;
-    use impls::session::consts::SESSION_COOKIE_NAME;
+
+    let site_config = match impls::site::init_site_config() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    };
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
 
     let server_addr = dioxus::cli_config::fullstack_address_or_localhost();
-    let is_production = std::env::var("APP_ENV").ok().as_deref() == Some("production");
-    let session_config = SessionConfig::default()
-        .with_session_name(SESSION_COOKIE_NAME)
-        .with_mode(SessionMode::OptIn)
-        .with_cookie_same_site(SameSite::Lax)
-        .with_http_only(true)
-        .with_secure(is_production);
-
-    let session_store = SessionStore::<axum_session::SessionNullPool>::new(None, session_config)
-        .await
-        .expect("init session store failed");
+    let session_store = match impls::session::init_session_store(site_config).await {
+        Ok(store) => store,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    };
 
     let router = axum::Router::new()
+        .merge(impls::feed::router())
+        .merge(impls::sitemap::router())
+        .merge(impls::search::router())
         .serve_dioxus_application(ServeConfig::default(), App)
+        .layer(axum::middleware::from_fn(
+            impls::locale_routes::redirect_to_locale,
+        ))
+        .layer(axum::middleware::from_fn(impls::http_cache::cache_headers))
         .layer(SessionLayer::new(session_store));
 
     let listener = tokio::net::TcpListener::bind(server_addr)
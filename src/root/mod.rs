@@ -4,9 +4,12 @@ mod routes;
 use crate::components::animated_bird::AnimatedBird;
 use crate::components::providers::interactive_provider::InteractiveProvider;
 use crate::components::providers::preference_provider::{
-    locale_to_langid, resolve_locale, resolve_theme, PreferenceProvider,
+    resolve_theme, resolve_theme_mode, PreferenceProvider,
 };
 use crate::impls::i18n as app_i18n;
+use crate::impls::session::consts::SYSTEM_THEME_COOKIE_NAME;
+use crate::impls::theme::{ThemeDef, SYSTEM_THEME};
+use crate::IO::site::get_site_info;
 use crate::IO::user::get_preference;
 use crate::IO::user::SessionPreferenceDto;
 use dioxus::document::{Link, Stylesheet};
@@ -19,7 +22,6 @@ const TW_STYLES: Asset = asset!("/assets/tailwind.css");
 const FONT_CSS: Asset = asset!("/assets/font.css");
 const MARKDOWN_CSS: Asset = asset!("/assets/markdown.css");
 const NOISE_IMAGE: Asset = asset!("/assets/noise.png");
-const MERMAID_JS_CDN: &str = "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.min.js";
 const IA_WRITER_QUATTRO_REGULAR: Asset = asset!(
     "/assets/fonts/iAWriterQuattroS-Regular.woff2",
     AssetOptions::builder()
@@ -65,21 +67,51 @@ const LXGW_REGULAR: Asset = asset!(
 
 #[allow(non_snake_case)]
 pub fn App() -> Element {
-    use_init_i18n(app_i18n::build_i18n_config);
     let preference_fut = use_server_future(get_preference)?;
+    let site_fut = use_server_future(get_site_info)?;
+    // Every page needs the locales, so a failed settings request is an error
+    // rather than a reason to guess them.
+    let Some(site_info) = site_fut() else {
+        return rsx! {};
+    };
+    let site_info = site_info?;
+    app_i18n::set_site_locales(&site_info);
+    use_init_i18n(app_i18n::build_i18n_config);
+    use_context_provider(|| site_info);
     let initial_preference = match preference_fut() {
         Some(Ok(preference)) => preference,
         _ => SessionPreferenceDto::default(),
     };
-    let initial_theme_str = resolve_theme(initial_preference.theme.as_deref());
-    let initial_locale_str = resolve_locale(initial_preference.locale.as_deref());
+    let initial_theme_mode = resolve_theme_mode(initial_preference.theme.as_deref());
+    let initial_theme = resolve_theme(
+        initial_preference.theme.as_deref(),
+        initial_preference.system_theme.as_deref(),
+    );
+    let initial_theme_id = initial_theme.id;
+    let initial_theme_attrs = theme_attributes(initial_theme);
+    let system_light_attrs = theme_attributes(resolve_theme(Some(SYSTEM_THEME), Some("light")));
+    let system_dark_attrs = theme_attributes(resolve_theme(Some(SYSTEM_THEME), Some("dark")));
+    let initial_locale = app_i18n::resolve_locale(initial_preference.locale.as_deref());
+    let initial_locale_str = initial_locale.tag();
     let mut i18n = i18n();
-    i18n.set_language(locale_to_langid(Some(initial_locale_str)));
+    i18n.set_language(initial_locale.langid.clone());
     let bootstrap_script = format!(
         r#"
 (function () {{
   var root = document.documentElement;
-  root.setAttribute("class", "{initial_theme_str}");
+  var theme = {initial_theme_attrs};
+  if ("{initial_theme_mode}" === "{SYSTEM_THEME}" && window.matchMedia) {{
+    var dark = window.matchMedia("(prefers-color-scheme: dark)").matches;
+    theme = dark ? {system_dark_attrs} : {system_light_attrs};
+    document.cookie = "{SYSTEM_THEME_COOKIE_NAME}=" + (dark ? "dark" : "light") + "; path=/; max-age=31536000; samesite=lax";
+    if (theme.id !== "{initial_theme_id}") {{
+      root.setAttribute("data-theme-pending", "");
+    }}
+  }}
+  root.classList.remove("light", "dark");
+  root.classList.add(theme.appearance);
+  root.setAttribute("data-theme", theme.id);
+  root.setAttribute("data-mermaid-theme", theme.mermaid);
   root.setAttribute("lang", "{initial_locale_str}");
 }})();
 "#
@@ -87,7 +119,6 @@ pub fn App() -> Element {
 
     rsx! {
         document::Script { {bootstrap_script} }
-        document::Script { src: MERMAID_JS_CDN }
         Link {
             rel: "preload",
             href: IA_WRITER_QUATTRO_REGULAR,
@@ -126,3 +157,13 @@ pub fn App() -> Element {
         }
     }
 }
+
+/// The `<html>` attributes for `theme`, as a JS object literal.
+fn theme_attributes(theme: &ThemeDef) -> String {
+    format!(
+        r#"{{ id: "{}", appearance: "{}", mermaid: "{}" }}"#,
+        theme.id,
+        theme.appearance.as_str(),
+        theme.mermaid_theme
+    )
+}
@@ -1,3 +1,5 @@
+use super::not_found::UnknownLocale;
+use crate::components::providers::preference_provider::is_site_locale;
 use crate::components::views::blog::list::BlogListView;
 use crate::components::views::blog::post::BlogPostView;
 use dioxus::prelude::*;
@@ -11,3 +13,19 @@ pub fn BlogList() -> Element {
 pub fn BlogPost(slug: String) -> Element {
     rsx! { BlogPostView { slug: slug } }
 }
+
+#[component]
+pub fn LocalizedBlogList(lang: String) -> Element {
+    if !is_site_locale(&lang) {
+        return rsx! { UnknownLocale {} };
+    }
+    rsx! { BlogListView { lang: lang } }
+}
+
+#[component]
+pub fn LocalizedBlogPost(lang: String, slug: String) -> Element {
+    if !is_site_locale(&lang) {
+        return rsx! { UnknownLocale {} };
+    }
+    rsx! { BlogPostView { slug: slug, lang: lang } }
+}
@@ -1,14 +1,20 @@
 mod about;
 mod blog;
 mod not_found;
+mod preview;
+mod search;
+mod series;
 mod tags;
 
 use super::layouts::{BlogLayout, RootLayout};
 use about::About;
-use blog::{BlogList, BlogPost};
+use blog::{BlogList, BlogPost, LocalizedBlogList, LocalizedBlogPost};
 use dioxus::prelude::*;
 use not_found::PageNotFound;
-use tags::{TagList, TagsTag};
+use preview::PostPreview;
+use search::Search;
+use series::{LocalizedSeriesDetail, SeriesDetail};
+use tags::{LocalizedTagList, LocalizedTagsTag, TagList, TagsTag};
 
 #[derive(Routable, Clone, PartialEq)]
 #[rustfmt::skip]
@@ -19,6 +25,12 @@ pub enum Route {
         #[route("/about")]
         About {},
 
+        #[route("/search?:q")]
+        Search { q: String },
+
+        #[route("/preview/:lang/:slug/:token")]
+        PostPreview { lang: String, slug: String, token: String },
+
         #[nest("/tags")]
           #[route("/")]
           TagList {},
@@ -26,6 +38,11 @@ pub enum Route {
           TagsTag { tag: String },
         #[end_nest]
 
+        #[nest("/series")]
+          #[route("/:id")]
+          SeriesDetail { id: String },
+        #[end_nest]
+
         #[nest("/blog")]
             #[layout(BlogLayout)]
                 #[route("/")]
@@ -35,8 +52,79 @@ pub enum Route {
                 BlogPost { slug: String },
             #[end_layout]
         #[end_nest]
+
+        // The same pages pinned to one language, e.g. `/en/blog/{slug}/`.
+        #[nest("/:lang")]
+            #[nest("/tags")]
+              #[route("/")]
+              LocalizedTagList { lang: String },
+              #[route("/:tag")]
+              LocalizedTagsTag { lang: String, tag: String },
+            #[end_nest]
+
+            #[nest("/series")]
+              #[route("/:id")]
+              LocalizedSeriesDetail { lang: String, id: String },
+            #[end_nest]
+
+            #[nest("/blog")]
+                #[layout(BlogLayout)]
+                    #[route("/")]
+                    LocalizedBlogList { lang: String },
+                    #[redirect("/:slug", |lang: String, slug: String| Route::LocalizedBlogPost { lang, slug })]
+                    #[route("/:slug/")]
+                    LocalizedBlogPost { lang: String, slug: String },
+                #[end_layout]
+            #[end_nest]
+        #[end_nest]
     #[end_layout]
 
     #[route("/:..route")]
     PageNotFound { route: Vec<String> },
 }
+
+impl Route {
+    /// Language fixed by a `/{lang}/...` URL.
+    pub fn locale(&self) -> Option<&str> {
+        match self {
+            Route::LocalizedTagList { lang }
+            | Route::LocalizedTagsTag { lang, .. }
+            | Route::LocalizedSeriesDetail { lang, .. }
+            | Route::LocalizedBlogList { lang }
+            | Route::LocalizedBlogPost { lang, .. } => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// The same page under `/{lang}/...`, or its unprefixed form for `None`.
+    /// Pages without a localized variant are returned unchanged.
+    pub fn localized(self, lang: Option<&str>) -> Route {
+        let Some(lang) = lang.map(str::to_string) else {
+            return match self {
+                Route::LocalizedTagList { .. } => Route::TagList {},
+                Route::LocalizedTagsTag { tag, .. } => Route::TagsTag { tag },
+                Route::LocalizedSeriesDetail { id, .. } => Route::SeriesDetail { id },
+                Route::LocalizedBlogList { .. } => Route::BlogList {},
+                Route::LocalizedBlogPost { slug, .. } => Route::BlogPost { slug },
+                route => route,
+            };
+        };
+
+        match self {
+            Route::TagList {} | Route::LocalizedTagList { .. } => Route::LocalizedTagList { lang },
+            Route::TagsTag { tag } | Route::LocalizedTagsTag { tag, .. } => {
+                Route::LocalizedTagsTag { lang, tag }
+            }
+            Route::SeriesDetail { id } | Route::LocalizedSeriesDetail { id, .. } => {
+                Route::LocalizedSeriesDetail { lang, id }
+            }
+            Route::BlogList {} | Route::LocalizedBlogList { .. } => {
+                Route::LocalizedBlogList { lang }
+            }
+            Route::BlogPost { slug } | Route::LocalizedBlogPost { slug, .. } => {
+                Route::LocalizedBlogPost { lang, slug }
+            }
+            route => route,
+        }
+    }
+}
@@ -1,7 +1,20 @@
 use crate::components::views::not_found::NotFoundView;
+use crate::root::Route;
 use dioxus::prelude::*;
 
 #[component]
 pub fn PageNotFound(route: Vec<String>) -> Element {
     rsx! { NotFoundView { route: route } }
 }
+
+/// Shown for `/{lang}/...` URLs in a language the site does not serve.
+#[component]
+pub fn UnknownLocale() -> Element {
+    let path = use_route::<Route>().to_string();
+    let route = path
+        .trim_matches('/')
+        .split('/')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    rsx! { NotFoundView { route: route } }
+}
@@ -0,0 +1,7 @@
+use crate::components::views::blog::preview::BlogPreviewView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn PostPreview(lang: String, slug: String, token: String) -> Element {
+    rsx! { BlogPreviewView { slug: slug, lang: lang, token: token } }
+}
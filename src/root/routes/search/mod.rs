@@ -0,0 +1,7 @@
+use crate::components::views::search::SearchView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn Search(q: String) -> Element {
+    rsx! { SearchView { q: q } }
+}
@@ -0,0 +1,17 @@
+use super::not_found::UnknownLocale;
+use crate::components::providers::preference_provider::is_site_locale;
+use crate::components::views::series::detail::SeriesView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SeriesDetail(id: String) -> Element {
+    rsx! { SeriesView { id: id } }
+}
+
+#[component]
+pub fn LocalizedSeriesDetail(lang: String, id: String) -> Element {
+    if !is_site_locale(&lang) {
+        return rsx! { UnknownLocale {} };
+    }
+    rsx! { SeriesView { id: id, lang: lang } }
+}
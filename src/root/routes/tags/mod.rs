@@ -1,3 +1,5 @@
+use super::not_found::UnknownLocale;
+use crate::components::providers::preference_provider::is_site_locale;
 use crate::components::views::tags::list::TagsListView;
 use crate::components::views::tags::tag::TagsTagView;
 use dioxus::prelude::*;
@@ -11,3 +13,19 @@ pub fn TagList() -> Element {
 pub fn TagsTag(tag: String) -> Element {
     rsx! { TagsTagView { tag: tag } }
 }
+
+#[component]
+pub fn LocalizedTagList(lang: String) -> Element {
+    if !is_site_locale(&lang) {
+        return rsx! { UnknownLocale {} };
+    }
+    rsx! { TagsListView { lang: lang } }
+}
+
+#[component]
+pub fn LocalizedTagsTag(lang: String, tag: String) -> Element {
+    if !is_site_locale(&lang) {
+        return rsx! { UnknownLocale {} };
+    }
+    rsx! { TagsTagView { tag: tag, lang: lang } }
+}
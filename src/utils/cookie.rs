@@ -0,0 +1,34 @@
+use axum::http::{header, HeaderMap};
+
+/// Value of the cookie called exactly `name` in the request's `Cookie`
+/// headers, trimmed.
+pub fn read_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then(|| value.trim())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn reads_cookies_by_exact_name() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::COOKIE,
+            HeaderValue::from_static("xtheme=light; theme= dark "),
+        );
+        headers.append(header::COOKIE, HeaderValue::from_static("other=1"));
+        assert_eq!(read_cookie(&headers, "theme"), Some("dark"));
+        assert_eq!(read_cookie(&headers, "other"), Some("1"));
+        assert_eq!(read_cookie(&headers, "missing"), None);
+    }
+}
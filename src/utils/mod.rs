@@ -1,2 +1,4 @@
+#[cfg(feature = "server")]
+pub mod cookie;
 pub mod markdown_toc;
 pub mod url;